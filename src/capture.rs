@@ -0,0 +1,103 @@
+//! Parsing captures of other hex dump tools, e.g. pasted from logs.
+
+use crate::parse::{Bytes, ParseError};
+
+/// The bytes of a capture of `xxd` or `hexdump -C`, like [`Xxd`](crate::Xxd)
+/// and [`HexdumpC`](crate::HexdumpC) write them.
+///
+/// Lines replaced by `*` repeat the line before them up to the offset of
+/// the next line, the trailing offset of `hexdump` is checked against the
+/// length. Blank lines and indentation are ignored.
+///
+/// ```rust
+/// use memdbg::{Capture, ParseError};
+///
+/// let mut buf = [0; 64];
+/// let capture = Capture::parse(concat!(
+///     "00001000: 0000 0000 0000 0000 0000 0000 0000 0000  ................\n",
+///     "*\n",
+///     "00001020: 5145                                     QE\n",
+/// ), &mut buf)?;
+/// assert_eq!(capture, Capture { base_offset: 0x1000, len: 34 });
+/// assert_eq!(&buf[32..34], b"QE");
+///
+/// let error = Capture::parse(concat!(
+///     "00000000  51 45 4d 55 20 20 20 20  |QEMU    |\n",
+///     "00000010  51 45 4d 55 20 20 20 20  |QEMU    |\n",
+/// ), &mut buf);
+/// assert_eq!(error, Err(ParseError::UnexpectedOffset { line: 2, expected: 8, found: 16 }));
+/// # Ok::<(), ParseError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capture {
+    /// The offset of the first byte.
+    pub base_offset: usize,
+    /// The number of bytes, stored at the start of the buffer.
+    pub len: usize,
+}
+
+impl Capture {
+    /// Parses the capture `s` into `buf`.
+    pub fn parse(s: &str, buf: &mut [u8]) -> Result<Self, ParseError> {
+        let mut bytes = Bytes { buf, len: 0 };
+        let mut base_offset = None;
+        // the bytes of the previous line, repeated by `*`
+        let mut previous = 0..0;
+        let mut squeezed = false;
+        for (i, line) in s.lines().enumerate() {
+            let number = i + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "*" && !previous.is_empty() {
+                squeezed = true;
+                continue;
+            }
+
+            let (offset, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let xxd = offset.ends_with(':');
+            let offset = offset.strip_suffix(':').unwrap_or(offset);
+            let offset = usize::from_str_radix(offset, 16)
+                .map_err(|_| ParseError::InvalidOffset { line: number })?;
+            let base = *base_offset.get_or_insert(offset);
+            let expected = base.wrapping_add(bytes.len);
+            if squeezed && offset > expected {
+                bytes.repeat(previous.clone(), offset - expected);
+            } else if offset != expected {
+                return Err(ParseError::UnexpectedOffset {
+                    line: number,
+                    expected,
+                    found: offset,
+                });
+            }
+            squeezed = false;
+
+            let start = bytes.len;
+            let words = match xxd {
+                // the characters of xxd follow the words after two spaces
+                true => rest.trim_start().split("  ").next().unwrap_or_default(),
+                // those of `hexdump -C` are enclosed in `|`
+                false => rest.split('|').next().unwrap_or_default(),
+            };
+            bytes
+                .push_words(words.split_whitespace())
+                .map_err(|error| error.at(number))?;
+            previous = start..bytes.len;
+        }
+        if squeezed {
+            return Err(ParseError::Truncated);
+        }
+        if bytes.len > bytes.buf.len() {
+            return Err(ParseError::Length {
+                expected: bytes.buf.len(),
+                found: bytes.len,
+            });
+        }
+
+        Ok(Self {
+            base_offset: base_offset.unwrap_or(0),
+            len: bytes.len,
+        })
+    }
+}
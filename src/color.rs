@@ -0,0 +1,82 @@
+//! ANSI colors for the `color` feature.
+
+use core::fmt::Write;
+
+pub(crate) const RESET: &str = "\x1b[0m";
+/// The background of shaded lines and groups.
+pub(crate) const SHADE: &str = "\x1b[48;5;236m";
+
+/// The class of a byte, which determines its color.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Class {
+    Zero,
+    Printable,
+    Control,
+    High,
+}
+
+impl Class {
+    const ALL: [Class; 4] = [Class::Zero, Class::Printable, Class::Control, Class::High];
+
+    pub(crate) fn of(byte: u8) -> Self {
+        match byte {
+            0 => Class::Zero,
+            _ if byte.is_ascii_graphic() => Class::Printable,
+            0x80.. => Class::High,
+            _ => Class::Control,
+        }
+    }
+
+    /// The class shared by all `bytes`, if any.
+    pub(crate) fn of_all(bytes: &[u8]) -> Option<Self> {
+        let class = Class::of(*bytes.first()?);
+        bytes
+            .iter()
+            .all(|&byte| Class::of(byte) == class)
+            .then_some(class)
+    }
+
+    pub(crate) const fn style(self) -> &'static str {
+        match self {
+            Class::Zero => "\x1b[2m",
+            Class::Printable => "\x1b[36m",
+            Class::Control => "\x1b[32m",
+            Class::High => "\x1b[33m",
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Class::Zero => "zero",
+            Class::Printable => "printable",
+            Class::Control => "control",
+            Class::High => "high-bit",
+        }
+    }
+}
+
+/// The name of the class or shading with the SGR parameters `sgr`.
+#[cfg(feature = "html")]
+pub(crate) fn class_name(sgr: &str) -> Option<&'static str> {
+    let parameters = |style: &'static str| style.strip_prefix("\x1b[")?.strip_suffix('m');
+    if parameters(SHADE) == Some(sgr) {
+        return Some("shade");
+    }
+    Class::ALL
+        .into_iter()
+        .find(|class| parameters(class.style()) == Some(sgr))
+        .map(Class::label)
+}
+
+/// Writes a line with the name of each class in its color.
+pub(crate) fn write_legend<W: Write + ?Sized>(f: &mut W) -> core::fmt::Result {
+    f.write_str("\n")?;
+    for (i, class) in Class::ALL.into_iter().enumerate() {
+        if i != 0 {
+            f.write_str(" ")?;
+        }
+        f.write_fmt(format_args!("{}{}{}", class.style(), class.label(), RESET))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use core::{
+    cmp::{max, min},
+    fmt::{Debug, Display, Formatter, Write},
+    ops::Range,
+};
+
+use crate::{
+    dump::{Indent, Width},
+    Dump, DumpConfig, Offsets,
+};
+
+/// Two hex dumps side by side, e.g. of the expected and the actual packet.
+///
+/// Lines that differ are separated by `≠`, equal lines by `=`. The
+/// precision of the formatter limits the number of bytes compared, its width
+/// indents the lines.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, SideBySide};
+///
+/// let expected = *b"GET /index.html HTTP/1.1";
+/// let actual = *b"GET /index.htm HTTP/1.1\0";
+/// let config = DumpConfig::portable().bytes_per_line(8);
+/// let compared = SideBySide::new(&expected, &actual, &config);
+/// assert_eq!(format!("{:?}", compared), concat!(
+///     "\n | 47 45 54 20 2F 69 6E 64 | GET./ind = | 47 45 54 20 2F 69 6E 64 | GET./ind",
+///     "\n | 65 78 2E 68 74 6D 6C 20 | ex.html. ≠ | 65 78 2E 68 74 6D 20 48 | ex.htm.H",
+///     "\n | 48 54 54 50 2F 31 2E 31 | HTTP/1.1 ≠ | 54 54 50 2F 31 2E 31 00 | TTP/1.1.",
+/// ));
+/// assert_eq!(format!("{:2.8?}", compared), concat!(
+///     "\n   | 47 45 54 20 2F 69 6E 64 | GET./ind",
+///     " = | 47 45 54 20 2F 69 6E 64 | GET./ind",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct SideBySide<'a> {
+    left: &'a [u8],
+    right: &'a [u8],
+    config: DumpConfig,
+}
+
+impl<'a> SideBySide<'a> {
+    /// Compares `left` to `right`, the offsets are displayed on the left.
+    pub fn new(left: &'a [u8], right: &'a [u8], config: &DumpConfig) -> Self {
+        Self {
+            left,
+            right,
+            config: *config,
+        }
+    }
+
+    /// Writes the lines, honoring the flags of the formatter like a [`Dump`].
+    ///
+    /// The precision limits the number of bytes of both sides, the width
+    /// indents every line with the fill character instead of
+    /// [`DumpConfig::indent`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // both sides start their lines at the same offsets
+        let config = self.config.address_alignment(false);
+        let len = f.precision().unwrap_or(usize::MAX);
+        let compared = Self {
+            left: &self.left[..min(len, self.left.len())],
+            right: &self.right[..min(len, self.right.len())],
+            config,
+        };
+        let (fill, width) = match f.width() {
+            Some(width) => (f.fill(), width),
+            None => (' ', config.indent),
+        };
+        if width == 0 {
+            return compared.write(f);
+        }
+        compared.write(&mut Indent {
+            inner: f,
+            fill,
+            width,
+        })
+    }
+
+    fn write<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        let config = self.config;
+        let left = Dump::new(self.left, &config);
+        let right = Dump::new(
+            self.right,
+            &DumpConfig {
+                offsets: Offsets::None,
+                line_prefix: "",
+                ..config
+            },
+        );
+        let line_len = config.bytes_per_line;
+        let lines = max(self.left.len(), self.right.len()).div_ceil(line_len);
+        let line = |i: usize, bytes: &[u8]| -> Range<usize> {
+            min(i * line_len, bytes.len())..min((i + 1) * line_len, bytes.len())
+        };
+
+        let mut width = 0;
+        for i in 0..lines {
+            let mut line_width = Width(0);
+            write_left(&mut line_width, &left, i * line_len, line(i, self.left))?;
+            width = max(width, line_width.0);
+        }
+        for i in 0..lines {
+            let (l, r) = (line(i, self.left), line(i, self.right));
+            f.write_str("\n")?;
+            let mut line_width = Width(0);
+            write_left(&mut line_width, &left, i * line_len, l.clone())?;
+            write_left(f, &left, i * line_len, l.clone())?;
+            for _ in line_width.0..width {
+                f.write_char(' ')?;
+            }
+            let equal = self.left[l] == self.right[r.clone()];
+            f.write_str(if equal { " =" } else { " ≠" })?;
+            right.write_line(f, r)?;
+            f.write_str(config.line_suffix)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the `range` of the left side with the offset of line `offset`.
+fn write_left<W: Write + ?Sized>(
+    f: &mut W,
+    left: &Dump<'_>,
+    offset: usize,
+    range: Range<usize>,
+) -> core::fmt::Result {
+    left.write_prefix(f, offset)?;
+    left.write_line(f, range)
+}
+
+impl Debug for SideBySide<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        SideBySide::fmt(self, f)
+    }
+}
+
+impl Display for SideBySide<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        SideBySide::fmt(self, f)
+    }
+}
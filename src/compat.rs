@@ -0,0 +1,567 @@
+//! The layouts of other hex dump tools, to compare and convert dumps with them.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::Endian;
+
+/// The layout of `xxd`, 16 bytes per line in groups of two, which `xxd -r`
+/// converts back into the bytes.
+///
+/// Unlike the other dumps, each line ends with a line break.
+///
+/// ```rust
+/// use memdbg::Xxd;
+///
+/// let dump = format!("{}", Xxd::new(b"0123456789abcdefghi\0\x7F ~"));
+/// assert_eq!(dump, concat!(
+///     "00000000: 3031 3233 3435 3637 3839 6162 6364 6566  0123456789abcdef\n",
+///     "00000010: 6768 6900 7f20 7e                        ghi.. ~\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Xxd<'a> {
+    bytes: &'a [u8],
+    base_offset: usize,
+    uppercase: bool,
+}
+
+impl<'a> Xxd<'a> {
+    /// Dumps `bytes` like `xxd` without options.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            base_offset: 0,
+            uppercase: false,
+        }
+    }
+
+    /// Adds `offset` to the displayed offsets, like `xxd -o`.
+    pub fn base_offset(mut self, offset: usize) -> Self {
+        self.base_offset = offset;
+        self
+    }
+
+    /// Uses uppercase hex digits, like `xxd -u`.
+    pub fn uppercase(mut self, enabled: bool) -> Self {
+        self.uppercase = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, line) in self.bytes.chunks(16).enumerate() {
+            let offset = self.base_offset.wrapping_add(16 * i);
+            f.write_fmt(format_args!("{:08x}:", offset))?;
+            for column in 0..16 {
+                if column % 2 == 0 {
+                    f.write_str(" ")?;
+                }
+                match line.get(column) {
+                    Some(byte) if self.uppercase => f.write_fmt(format_args!("{:02X}", byte))?,
+                    Some(byte) => f.write_fmt(format_args!("{:02x}", byte))?,
+                    None => f.write_str("  ")?,
+                }
+            }
+            f.write_str("  ")?;
+            write_ascii(f, line)?;
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Xxd<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Xxd::fmt(self, f)
+    }
+}
+
+impl Display for Xxd<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Xxd::fmt(self, f)
+    }
+}
+
+/// The canonical layout of `hexdump -C`, 16 bytes per line in two halves
+/// and the characters between `|`.
+///
+/// Repeated lines are replaced by `*` like `hexdump` does without `-v`, the
+/// last line is the offset after the end. Each line ends with a line break.
+///
+/// ```rust
+/// use memdbg::HexdumpC;
+///
+/// let dump = format!("{}", HexdumpC::new(b"0123456789abcdefghi\0\x7F ~"));
+/// assert_eq!(dump, concat!(
+///     "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|\n",
+///     "00000010  67 68 69 00 7f 20 7e                              |ghi.. ~|\n",
+///     "00000017\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexdumpC<'a> {
+    bytes: &'a [u8],
+    base_offset: usize,
+    verbose: bool,
+}
+
+impl<'a> HexdumpC<'a> {
+    /// Dumps `bytes` like `hexdump -C`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            base_offset: 0,
+            verbose: false,
+        }
+    }
+
+    /// Adds `offset` to the displayed offsets, like `hexdump -s` does for
+    /// the skipped bytes.
+    pub fn base_offset(mut self, offset: usize) -> Self {
+        self.base_offset = offset;
+        self
+    }
+
+    /// Writes repeated lines instead of `*`, like `hexdump -v`.
+    pub fn verbose(mut self, enabled: bool) -> Self {
+        self.verbose = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut previous = None;
+        let mut squeezed = false;
+        for (i, line) in self.bytes.chunks(16).enumerate() {
+            if !self.verbose && previous == Some(line) {
+                if !squeezed {
+                    f.write_str("*\n")?;
+                }
+                squeezed = true;
+                continue;
+            }
+            previous = Some(line);
+            squeezed = false;
+
+            let offset = self.base_offset.wrapping_add(16 * i);
+            f.write_fmt(format_args!("{:08x} ", offset))?;
+            for column in 0..16 {
+                if column % 8 == 0 {
+                    f.write_str(" ")?;
+                }
+                match line.get(column) {
+                    Some(byte) => f.write_fmt(format_args!("{:02x} ", byte))?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            f.write_str(" |")?;
+            write_ascii(f, line)?;
+            f.write_str("|\n")?;
+        }
+        if self.bytes.is_empty() {
+            return Ok(());
+        }
+
+        let end = self.base_offset.wrapping_add(self.bytes.len());
+        f.write_fmt(format_args!("{:08x}\n", end))
+    }
+}
+
+impl Debug for HexdumpC<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        HexdumpC::fmt(self, f)
+    }
+}
+
+impl Display for HexdumpC<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        HexdumpC::fmt(self, f)
+    }
+}
+
+/// The radix of the offsets and words of [`Od`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OdRadix {
+    /// `-A o` and `-t o`.
+    Octal,
+    /// `-A d` and the unsigned `-t u`.
+    Decimal,
+    /// `-A x` and `-t x`.
+    Hex,
+}
+
+/// The layout of `od` with a single output type, `od -A x -t x1` for
+/// `Od::new(bytes).offsets(Some(OdRadix::Hex)).words(OdRadix::Hex, 1)`.
+///
+/// Repeated lines are replaced by `*` like `od` does without `-v`, the last
+/// line is the offset after the end. Each line ends with a line break.
+///
+/// ```rust
+/// use memdbg::{Od, OdRadix};
+///
+/// let od = Od::new(b"0123456789abcdefghi").offsets(Some(OdRadix::Hex)).words(OdRadix::Hex, 1);
+/// assert_eq!(format!("{od}"), concat!(
+///     "000000 30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66\n",
+///     "000010 67 68 69\n",
+///     "000013\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Od<'a> {
+    bytes: &'a [u8],
+    offsets: Option<OdRadix>,
+    radix: OdRadix,
+    word_size: usize,
+    endian: Endian,
+    base_offset: usize,
+    verbose: bool,
+}
+
+impl<'a> Od<'a> {
+    /// Dumps `bytes` like `od` without options, `od -A o -t o2`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            offsets: Some(OdRadix::Octal),
+            radix: OdRadix::Octal,
+            word_size: 2,
+            endian: Endian::Native,
+            base_offset: 0,
+            verbose: false,
+        }
+    }
+
+    /// Sets the radix of the offsets like `-A`, `None` omits them like `-A n`.
+    pub fn offsets(mut self, radix: Option<OdRadix>) -> Self {
+        self.offsets = radix;
+        self
+    }
+
+    /// Displays words of `bytes` bytes in `radix` like `-t`, e.g. `-t x4` for
+    /// `words(OdRadix::Hex, 4)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not 1, 2, 4 or 8.
+    pub fn words(mut self, radix: OdRadix, bytes: usize) -> Self {
+        assert!(
+            matches!(bytes, 1 | 2 | 4 | 8),
+            "word size must be 1, 2, 4 or 8"
+        );
+        self.radix = radix;
+        self.word_size = bytes;
+        self
+    }
+
+    /// Sets the byte order of the words like `--endian`, the byte order of
+    /// the target by default.
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Adds `offset` to the displayed offsets, like `od -j` does for the
+    /// skipped bytes.
+    pub fn base_offset(mut self, offset: usize) -> Self {
+        self.base_offset = offset;
+        self
+    }
+
+    /// Writes repeated lines instead of `*`, like `od -v`.
+    pub fn verbose(mut self, enabled: bool) -> Self {
+        self.verbose = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut previous = None;
+        let mut squeezed = false;
+        for (i, line) in self.bytes.chunks(16).enumerate() {
+            if !self.verbose && previous == Some(line) {
+                if !squeezed {
+                    f.write_str("*\n")?;
+                }
+                squeezed = true;
+                continue;
+            }
+            previous = Some(line);
+            squeezed = false;
+
+            self.write_offset(f, 16 * i)?;
+            for word in line.chunks(self.word_size) {
+                f.write_str(" ")?;
+                self.write_word(f, word)?;
+            }
+            f.write_str("\n")?;
+        }
+        if self.offsets.is_none() {
+            return Ok(());
+        }
+
+        self.write_offset(f, self.bytes.len())?;
+        f.write_str("\n")
+    }
+
+    fn write_offset<W: Write + ?Sized>(&self, f: &mut W, offset: usize) -> core::fmt::Result {
+        let offset = self.base_offset.wrapping_add(offset);
+        match self.offsets {
+            None => Ok(()),
+            Some(OdRadix::Octal) => f.write_fmt(format_args!("{:07o}", offset)),
+            Some(OdRadix::Decimal) => f.write_fmt(format_args!("{:07}", offset)),
+            Some(OdRadix::Hex) => f.write_fmt(format_args!("{:06x}", offset)),
+        }
+    }
+
+    /// Writes a word, a trailing partial word is padded with zero bytes.
+    fn write_word<W: Write + ?Sized>(&self, f: &mut W, bytes: &[u8]) -> core::fmt::Result {
+        let size = self.word_size;
+        let mut word = [0; 8];
+        word[..bytes.len()].copy_from_slice(bytes);
+        let value = if self.endian.is_little() {
+            u64::from_le_bytes(word)
+        } else {
+            u64::from_be_bytes(word) >> (64 - 8 * size)
+        };
+        match self.radix {
+            OdRadix::Octal => {
+                let width = (8 * size).div_ceil(3);
+                f.write_fmt(format_args!("{:0width$o}", value))
+            }
+            OdRadix::Decimal => {
+                let width = [3, 5, 10, 20][size.trailing_zeros() as usize];
+                f.write_fmt(format_args!("{:width$}", value))
+            }
+            OdRadix::Hex => f.write_fmt(format_args!("{:0width$x}", value, width = 2 * size)),
+        }
+    }
+}
+
+impl Debug for Od<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Od::fmt(self, f)
+    }
+}
+
+impl Display for Od<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Od::fmt(self, f)
+    }
+}
+
+/// The layout Wireshark copies packet bytes in and reads back with
+/// "Import from Hex Dump", 16 bytes per line after the offset.
+///
+/// Each line ends with a line break. Offsets start at 0 for every packet,
+/// so dump each packet separately.
+///
+/// ```rust
+/// use memdbg::Wireshark;
+///
+/// let packet = b"\x45\x00\x00\x1cGET / HTTP/1.1\r\n";
+/// assert_eq!(format!("{}", Wireshark::new(packet).ascii(true)), concat!(
+///     "0000   45 00 00 1c 47 45 54 20 2f 20 48 54 54 50 2f 31   E...GET / HTTP/1\n",
+///     "0010   2e 31 0d 0a                                       .1..\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Wireshark<'a> {
+    bytes: &'a [u8],
+    ascii: bool,
+}
+
+impl<'a> Wireshark<'a> {
+    /// Dumps the packet `bytes` like "Copy as Hex Dump".
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            ascii: false,
+        }
+    }
+
+    /// Appends the characters like "Copy as Hex + ASCII Dump".
+    pub fn ascii(mut self, enabled: bool) -> Self {
+        self.ascii = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, line) in self.bytes.chunks(16).enumerate() {
+            f.write_fmt(format_args!("{:04x}  ", 16 * i))?;
+            line.iter()
+                .try_for_each(|byte| f.write_fmt(format_args!(" {:02x}", byte)))?;
+            if self.ascii {
+                (line.len()..16).try_for_each(|_| f.write_str("   "))?;
+                f.write_str("   ")?;
+                write_ascii(f, line)?;
+            }
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Wireshark<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Wireshark::fmt(self, f)
+    }
+}
+
+impl Display for Wireshark<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Wireshark::fmt(self, f)
+    }
+}
+
+/// The format letter of [`Gdb`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GdbFormat {
+    /// `x`, zero padded hex.
+    Hex,
+    /// `d`, signed decimal.
+    Decimal,
+    /// `u`, unsigned decimal.
+    Unsigned,
+}
+
+/// The layout of gdb's `x` command, `x/16xb buf` for
+/// `Gdb::new(&buf).words(GdbFormat::Hex, 1).symbol("buf")`.
+///
+/// Lines start with the address of their first word and hold as many words
+/// as gdb shows. Each line ends with a line break.
+///
+/// ```rust
+/// use memdbg::{Gdb, GdbFormat};
+///
+/// let gdb = Gdb::new(b"ABCDEFGHIJKLMNOPQ").address(0x7fffffffe3d0).symbol("buf");
+/// assert_eq!(format!("{gdb}"), concat!(
+///     "0x7fffffffe3d0 <buf>:\t0x41\t0x42\t0x43\t0x44\t0x45\t0x46\t0x47\t0x48\n",
+///     "0x7fffffffe3d8 <buf+8>:\t0x49\t0x4a\t0x4b\t0x4c\t0x4d\t0x4e\t0x4f\t0x50\n",
+///     "0x7fffffffe3e0 <buf+16>:\t0x51\n",
+/// ));
+/// let gdb = Gdb::new(&[0xFF; 8]).address(0x1000).words(GdbFormat::Decimal, 4);
+/// assert_eq!(format!("{gdb}"), "0x1000:\t-1\t-1\n");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Gdb<'a> {
+    bytes: &'a [u8],
+    address: usize,
+    symbol: Option<&'a str>,
+    format: GdbFormat,
+    word_size: usize,
+    endian: Endian,
+}
+
+impl<'a> Gdb<'a> {
+    /// Dumps `bytes` like `x/xb`, at their address.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            address: bytes.as_ptr() as usize,
+            symbol: None,
+            format: GdbFormat::Hex,
+            word_size: 1,
+            endian: Endian::Native,
+        }
+    }
+
+    /// Displays `address` instead of the address of the bytes, e.g. the
+    /// address on the device they were read from.
+    pub fn address(mut self, address: usize) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Displays the offsets from `symbol` after the addresses, `<buf+8>`.
+    pub fn symbol(mut self, symbol: &'a str) -> Self {
+        self.symbol = Some(symbol);
+        self
+    }
+
+    /// Displays words of `bytes` bytes in `format`, e.g. `x/dw` for
+    /// `words(GdbFormat::Decimal, 4)`. A trailing partial word is padded
+    /// with zero bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not 1, 2, 4 or 8.
+    pub fn words(mut self, format: GdbFormat, bytes: usize) -> Self {
+        assert!(
+            matches!(bytes, 1 | 2 | 4 | 8),
+            "word size must be 1, 2, 4 or 8"
+        );
+        self.format = format;
+        self.word_size = bytes;
+        self
+    }
+
+    /// Sets the byte order of the words, the byte order of the target by default.
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let size = self.word_size;
+        // gdb fits 8 bytes, halfwords and 4 words or 2 giant words on a line
+        let line_len = match size {
+            1 | 2 => 8 * size,
+            _ => 16,
+        };
+        for (i, line) in self.bytes.chunks(line_len).enumerate() {
+            let offset = line_len * i;
+            f.write_fmt(format_args!("{:#x}", self.address.wrapping_add(offset)))?;
+            match self.symbol {
+                Some(symbol) if offset == 0 => f.write_fmt(format_args!(" <{}>", symbol))?,
+                Some(symbol) => f.write_fmt(format_args!(" <{}+{}>", symbol, offset))?,
+                None => {}
+            }
+            f.write_str(":")?;
+            for word in line.chunks(size) {
+                let mut bytes = [0; 8];
+                bytes[..word.len()].copy_from_slice(word);
+                let value = if self.endian.is_little() {
+                    u64::from_le_bytes(bytes)
+                } else {
+                    u64::from_be_bytes(bytes) >> (64 - 8 * size)
+                };
+                match self.format {
+                    GdbFormat::Hex => {
+                        f.write_fmt(format_args!("\t0x{:0width$x}", value, width = 2 * size))?
+                    }
+                    GdbFormat::Decimal => {
+                        // sign extend the word
+                        let shift = 64 - 8 * size;
+                        let value = ((value << shift) as i64) >> shift;
+                        f.write_fmt(format_args!("\t{}", value))?
+                    }
+                    GdbFormat::Unsigned => f.write_fmt(format_args!("\t{}", value))?,
+                }
+            }
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Gdb<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Gdb::fmt(self, f)
+    }
+}
+
+impl Display for Gdb<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Gdb::fmt(self, f)
+    }
+}
+
+/// Writes printable ASCII and the space as is and everything else as `.`.
+fn write_ascii<W: Write + ?Sized>(f: &mut W, bytes: &[u8]) -> core::fmt::Result {
+    bytes.iter().try_for_each(|&byte| {
+        f.write_char(match byte {
+            b' '..=b'~' => byte as char,
+            _ => '.',
+        })
+    })
+}
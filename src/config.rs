@@ -0,0 +1,1151 @@
+use crate::{dump, Charset};
+
+/// Layout options for a hex dump, see [`Buf::display_with`](crate::Buf::display_with).
+///
+/// The default configuration matches the layout of [`Buf`](crate::Buf)'s Debug impl.
+/// Options are set with chained builder methods:
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig, Offsets};
+///
+/// let config = DumpConfig::portable()
+///     .bytes_per_line(16)
+///     .group_size(4)
+///     .offsets(Offsets::Hex(8))
+///     .text_column(false);
+/// assert_eq!(format!("{:?}", Buf(*b"a configurable hex dump").display_with(&config)), concat!(
+///     "\n00000000: | 61 20 63 6F | 6E 66 69 67 | 75 72 61 62 | 6C 65 20 68",
+///     "\n00000010: | 65 78 20 64 | 75 6D 70",
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DumpConfig {
+    pub(crate) bytes_per_line: usize,
+    pub(crate) group_size: usize,
+    pub(crate) address_alignment: bool,
+    pub(crate) word_size: usize,
+    pub(crate) endian: Endian,
+    pub(crate) radix: Radix,
+    pub(crate) nibble_swap: bool,
+    pub(crate) offsets: Offsets,
+    pub(crate) base_offset: usize,
+    pub(crate) notation: Notation,
+    pub(crate) offset_notation: Notation,
+    pub(crate) offset_uppercase: Option<bool>,
+    pub(crate) hex_column: bool,
+    pub(crate) text_column: bool,
+    pub(crate) text_first: bool,
+    pub(crate) header: bool,
+    pub(crate) checksum: Checksum,
+    pub(crate) values: Option<Values>,
+    pub(crate) group_separator: &'static str,
+    pub(crate) column_separator: &'static str,
+    pub(crate) line_prefix: &'static str,
+    pub(crate) line_suffix: &'static str,
+    pub(crate) indent: usize,
+    pub(crate) squeeze: bool,
+    pub(crate) squeeze_context: bool,
+    pub(crate) preview: Option<usize>,
+    pub(crate) zero_runs: Option<usize>,
+    pub(crate) boundaries: Option<usize>,
+    pub(crate) summary: bool,
+    pub(crate) reverse: bool,
+    pub(crate) right_align_last: bool,
+    pub(crate) placeholder: char,
+    pub(crate) null_placeholder: Option<char>,
+    pub(crate) line_endings: bool,
+    pub(crate) charset: Charset,
+    pub(crate) continuation_placeholder: char,
+    #[cfg(feature = "color")]
+    pub(crate) color: bool,
+    #[cfg(feature = "color")]
+    pub(crate) color_legend: bool,
+    #[cfg(feature = "color")]
+    pub(crate) shading: Shading,
+    pub(crate) uppercase: bool,
+    pub(crate) canonical: bool,
+    pub(crate) plain: bool,
+    pub(crate) box_drawing: bool,
+}
+
+/// The offset column at the start of each line, akin to `xxd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offsets {
+    /// No offset column.
+    None,
+    /// Hexadecimal offsets, zero padded to the given number of digits.
+    Hex(usize),
+    /// Decimal offsets, zero padded to the given number of digits.
+    Decimal(usize),
+    /// The runtime address of the first byte of each line, in hexadecimal.
+    ///
+    /// Useful to cross-reference a dump with debugger watchpoints. The bytes
+    /// of a [`Dumper`](crate::Dumper) or an [`IterDump`](crate::IterDump)
+    /// have no address, they show their offsets instead.
+    Address,
+}
+
+/// How hexadecimal numbers are marked, see [`DumpConfig::notation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// `AB`.
+    Plain,
+    /// `0xAB`, as in C.
+    Prefix,
+    /// `ABh`, as in assembler listings.
+    Suffix,
+}
+
+impl Notation {
+    /// The text in front of and after the digits.
+    pub(crate) const fn affixes(self) -> (&'static str, &'static str) {
+        match self {
+            Notation::Plain => ("", ""),
+            Notation::Prefix => ("0x", ""),
+            Notation::Suffix => ("", "h"),
+        }
+    }
+
+    pub(crate) const fn len(self) -> usize {
+        let (prefix, suffix) = self.affixes();
+        prefix.len() + suffix.len()
+    }
+}
+
+/// A checksum appended to each line, see [`DumpConfig::checksum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Checksum {
+    /// No checksum column.
+    None,
+    /// The wrapping sum of the bytes.
+    Sum,
+    /// CRC-8 with polynomial `0x07` and initial value 0, as used by SMBus.
+    Crc8,
+}
+
+/// The interpretation of the bytes in the values column, see [`DumpConfig::values`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Values {
+    /// `f32`, 4 bytes each.
+    F32,
+    /// `f64`, 8 bytes each.
+    F64,
+    /// `i8`, 1 byte each.
+    I8,
+    /// `i16`, 2 bytes each.
+    I16,
+    /// `i32`, 4 bytes each.
+    I32,
+    /// `i64`, 8 bytes each.
+    I64,
+    /// `u8`, 1 byte each.
+    U8,
+    /// `u16`, 2 bytes each.
+    U16,
+    /// `u32`, 4 bytes each.
+    U32,
+    /// `u64`, 8 bytes each.
+    U64,
+}
+
+impl Values {
+    /// Number of bytes per value.
+    pub(crate) const fn size(self) -> usize {
+        match self {
+            Values::I8 | Values::U8 => 1,
+            Values::I16 | Values::U16 => 2,
+            Values::F32 | Values::I32 | Values::U32 => 4,
+            Values::F64 | Values::I64 | Values::U64 => 8,
+        }
+    }
+}
+
+/// Alternating background shading in ANSI mode, see [`DumpConfig::shading`].
+#[cfg(feature = "color")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shading {
+    /// No shading.
+    None,
+    /// Every other line.
+    Lines,
+    /// Every other group of the hex column.
+    Groups,
+}
+
+/// The byte order used to read words, see [`DumpConfig::word_size`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// The byte order of the target.
+    Native,
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+/// The base in which bytes and words are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Radix {
+    /// Hexadecimal, `41`.
+    Hex,
+    /// Octal, `101`, as printed by `od`.
+    Octal,
+    /// Binary, `01000001`.
+    Binary,
+    /// Space separated bits, `0 1 0 0 0 0 0 1`, for flag registers and bitfields.
+    ///
+    /// The [`header`](DumpConfig::header) shows the index of each bit
+    /// within its byte.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Radix};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .radix(Radix::Bits)
+    ///     .bytes_per_line(2)
+    ///     .group_size(1)
+    ///     .header(true);
+    /// assert_eq!(format!("{:?}", Buf([0x81, 0x05]).display_with(&config)), concat!(
+    ///     " | 7 6 5 4 3 2 1 0 | 7 6 5 4 3 2 1 0 | 01",
+    ///     "\n | 1 0 0 0 0 0 0 1 | 0 0 0 0 0 1 0 1 | ..",
+    /// ));
+    /// ```
+    Bits,
+}
+
+impl DumpConfig {
+    /// Number of characters needed for a word of `bytes` bytes, including its
+    /// [`notation`](Self::notation).
+    pub(crate) const fn digits(&self, bytes: usize) -> usize {
+        match self.radix {
+            Radix::Hex => self.radix.digits(bytes) + self.notation.len(),
+            radix => radix.digits(bytes),
+        }
+    }
+}
+
+impl Radix {
+    /// Number of digits needed for `bytes` bytes.
+    pub(crate) const fn digits(self, bytes: usize) -> usize {
+        match self {
+            Radix::Hex => 2 * bytes,
+            Radix::Octal => (8 * bytes).div_ceil(3),
+            Radix::Binary => 8 * bytes,
+            Radix::Bits => 16 * bytes - 1,
+        }
+    }
+}
+
+impl Checksum {
+    pub(crate) fn of(self, bytes: &[u8]) -> Option<u8> {
+        match self {
+            Checksum::None => None,
+            Checksum::Sum => Some(bytes.iter().fold(0, |sum, &byte| sum.wrapping_add(byte))),
+            Checksum::Crc8 => Some(bytes.iter().fold(0, |crc, &byte| {
+                (0..8).fold(crc ^ byte, |crc, _| {
+                    if crc & 0x80 != 0 {
+                        crc << 1 ^ 0x07
+                    } else {
+                        crc << 1
+                    }
+                })
+            })),
+        }
+    }
+}
+
+impl Endian {
+    pub(crate) const fn is_little(self) -> bool {
+        match self {
+            Endian::Native => cfg!(target_endian = "little"),
+            Endian::Little => true,
+            Endian::Big => false,
+        }
+    }
+}
+
+impl DumpConfig {
+    /// The default layout, 32 bytes per line grouped by the alignment of `usize`.
+    pub const fn new() -> Self {
+        Self {
+            bytes_per_line: 32,
+            group_size: core::mem::align_of::<usize>(),
+            address_alignment: true,
+            word_size: 1,
+            endian: Endian::Native,
+            radix: Radix::Hex,
+            nibble_swap: false,
+            offsets: Offsets::None,
+            base_offset: 0,
+            notation: Notation::Plain,
+            offset_notation: Notation::Plain,
+            offset_uppercase: None,
+            hex_column: true,
+            text_column: true,
+            text_first: false,
+            header: false,
+            checksum: Checksum::None,
+            values: None,
+            group_separator: "|",
+            column_separator: "|",
+            line_prefix: "",
+            line_suffix: "",
+            indent: 0,
+            squeeze: false,
+            squeeze_context: false,
+            preview: None,
+            zero_runs: None,
+            boundaries: None,
+            summary: false,
+            reverse: false,
+            right_align_last: false,
+            placeholder: '.',
+            null_placeholder: None,
+            line_endings: false,
+            charset: Charset::Ascii,
+            continuation_placeholder: '·',
+            #[cfg(feature = "color")]
+            color: false,
+            #[cfg(feature = "color")]
+            color_legend: false,
+            #[cfg(feature = "color")]
+            shading: Shading::None,
+            uppercase: true,
+            canonical: false,
+            plain: false,
+            box_drawing: false,
+        }
+    }
+
+    /// A layout that does not depend on the target or the address of the
+    /// bytes, e.g. for golden file tests.
+    ///
+    /// Groups of 8 bytes start at the first byte instead of aligned
+    /// addresses. Avoid [`Offsets::Address`] for portable output.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"portable");
+    /// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(8));
+    /// assert_eq!(format!("{dump:?}"), "\n | 70 6F 72 74 61 62 6C 65 | portable");
+    /// ```
+    pub const fn portable() -> Self {
+        Self::new().group_size(8).address_alignment(false)
+    }
+
+    /// A [`portable`](Self::portable) layout with offsets for textual diffs
+    /// of dumps.
+    ///
+    /// The first line is not preceded by a line break, no line has trailing
+    /// whitespace and every column starts at the same position on each line.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"diff friendly");
+    /// let dump = buf.display_with(&DumpConfig::canonical().bytes_per_line(8));
+    /// assert_eq!(
+    ///     format!("{dump:?}"),
+    ///     "00000000: | 64 69 66 66 20 66 72 69 | diff.fri\n\
+    ///      00000008: | 65 6E 64 6C 79          | endly",
+    /// );
+    /// ```
+    pub const fn canonical() -> Self {
+        let mut config = Self::portable().offsets(Offsets::Hex(8));
+        config.canonical = true;
+        config
+    }
+
+    /// Sets the number of bytes displayed per line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is 0.
+    pub const fn bytes_per_line(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "bytes per line must not be 0");
+        self.bytes_per_line = bytes;
+        self
+    }
+
+    /// Picks the largest [`bytes_per_line`](Self::bytes_per_line), a
+    /// multiple of the group size, whose lines fit into `columns` characters,
+    /// but at least one group.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().fit_width(80);
+    /// let dump = format!("{:?}", Buf([0x5A; 64]).display_with(&config));
+    /// assert!(dump.lines().all(|line| line.chars().count() <= 80));
+    /// assert_eq!(dump.lines().count(), 1 + 4);
+    /// ```
+    pub fn fit_width(self, columns: usize) -> Self {
+        let mut fit = self.group_size;
+        let mut len = 2 * self.group_size;
+        while len <= dump::MAX_LINE && dump::line_width(&self.bytes_per_line(len)) <= columns {
+            fit = len;
+            len += self.group_size;
+        }
+        self.bytes_per_line(fit)
+    }
+
+    /// [Fits](Self::fit_width) the lines into the terminal if standard
+    /// output is one.
+    ///
+    /// The width is queried from the terminal, with `ioctl` on Unix and the
+    /// console screen buffer on Windows, and read from the `COLUMNS`
+    /// environment variable if the terminal does not answer. Keeps the bytes
+    /// per line if standard output is not a terminal or its width is unknown.
+    ///
+    /// ```rust
+    /// use memdbg::DumpConfig;
+    /// use std::io::IsTerminal;
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(16);
+    /// if !std::io::stdout().is_terminal() {
+    ///     assert_eq!(config.fit_terminal(), config);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn fit_terminal(self) -> Self {
+        match crate::terminal::width() {
+            Some(columns) => self.fit_width(columns),
+            None => self,
+        }
+    }
+
+    /// Sets the number of bytes between two `|` separators, the alignment
+    /// of `usize` by default.
+    ///
+    /// Groups start at addresses that are a multiple of the group size, the
+    /// bytes before the first such address are displayed on a line of
+    /// their own.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"any size");
+    /// let expected = [
+    ///     "\n | 61 | 6E | 79 | 20 | 73 | 69 | 7A | 65 | any.size",
+    ///     "\n | 61 6E | 79 20 | 73 69 | 7A 65 | any.size",
+    ///     "\n | 61 6E 79 20 | 73 69 7A 65 | any.size",
+    ///     "\n | 61 6E 79 20 73 69 7A 65 | any.size",
+    /// ];
+    /// for (size, expected) in [1, 2, 4, 8].into_iter().zip(expected) {
+    ///     let config = DumpConfig::portable().bytes_per_line(8).group_size(size);
+    ///     assert_eq!(format!("{:?}", buf.display_with(&config)), expected);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is 0.
+    pub const fn group_size(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "group size must not be 0");
+        self.group_size = bytes;
+        self
+    }
+
+    /// Starts groups at addresses that are a multiple of the group size,
+    /// enabled by default.
+    ///
+    /// When disabled, groups start at the first byte and the output no
+    /// longer depends on the address of the bytes.
+    pub const fn address_alignment(mut self, enabled: bool) -> Self {
+        self.address_alignment = enabled;
+        self
+    }
+
+    /// Displays the bytes as words of `bytes` bytes instead of individually,
+    /// e.g. `20682041` instead of `41 20 68 20` for 4 in little endian.
+    ///
+    /// The byte order is set with [`endian`](Self::endian). The group size
+    /// should be a multiple of the word size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is not 1, 2, 4 or 8.
+    pub const fn word_size(mut self, bytes: usize) -> Self {
+        assert!(
+            matches!(bytes, 1 | 2 | 4 | 8),
+            "word size must be 1, 2, 4 or 8"
+        );
+        self.word_size = bytes;
+        self
+    }
+
+    /// Sets the byte order used to read words, [`Endian::Native`] by default.
+    pub const fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Sets the base in which bytes and words are displayed, [`Radix::Hex`] by default.
+    pub const fn radix(mut self, radix: Radix) -> Self {
+        self.radix = radix;
+        self
+    }
+
+    /// Swaps the nibbles of each displayed byte, `1A` is shown as `A1`.
+    ///
+    /// Useful for BCD data and flash controllers that store nibbles swapped.
+    /// The character and values columns still use the original bytes.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let bcd = Buf([0x21, 0x43, 0x65]);
+    /// let config = DumpConfig::portable().bytes_per_line(3).nibble_swap(true);
+    /// assert_eq!(format!("{:?}", bcd.display_with(&config)), "\n | 12 34 56 | !Ce");
+    /// ```
+    pub const fn nibble_swap(mut self, enabled: bool) -> Self {
+        self.nibble_swap = enabled;
+        self
+    }
+
+    /// Sets the offset column printed at the start of each line.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(8).offsets(Offsets::Hex(8));
+    /// assert_eq!(format!("{:?}", Buf([0u8; 20]).display_with(&config)), concat!(
+    ///     "\n00000000: | 00 00 00 00 00 00 00 00 | ........",
+    ///     "\n00000008: | 00 00 00 00 00 00 00 00 | ........",
+    ///     "\n00000010: | 00 00 00 00             | ....",
+    /// ));
+    /// ```
+    pub const fn offsets(mut self, offsets: Offsets) -> Self {
+        self.offsets = offsets;
+        self
+    }
+
+    /// Sets the separator written before each group of bytes, `|` by default.
+    ///
+    /// Separators are preceded by a space, or the closing bracket of a
+    /// [highlight](crate::Dump::highlight).
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .group_separator("")
+    ///     .column_separator("||")
+    ///     .line_prefix("    ");
+    /// let buf = Buf(*b"matches the in-house log format");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n      6D 61 74 63 68 65 73 20  74 68 65 20 69 6E 2D 68 || matches.the.in-h",
+    ///     "\n      6F 75 73 65 20 6C 6F 67  20 66 6F 72 6D 61 74    || ouse.log.format",
+    /// ));
+    /// ```
+    pub const fn group_separator(mut self, separator: &'static str) -> Self {
+        self.group_separator = separator;
+        self
+    }
+
+    /// Sets the separator between the hex column, the character column and
+    /// the checksum, `|` by default.
+    pub const fn column_separator(mut self, separator: &'static str) -> Self {
+        self.column_separator = separator;
+        self
+    }
+
+    /// Sets a string written at the start of each line, before the offset.
+    pub const fn line_prefix(mut self, prefix: &'static str) -> Self {
+        self.line_prefix = prefix;
+        self
+    }
+
+    /// Sets a string written at the end of each line.
+    pub const fn line_suffix(mut self, suffix: &'static str) -> Self {
+        self.line_suffix = suffix;
+        self
+    }
+
+    /// Starts counting [`Offsets::Hex`] and [`Offsets::Decimal`] at `base`
+    /// instead of 0, e.g. the position of the bytes in a file or the base
+    /// address of a peripheral.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .offsets(Offsets::Hex(8))
+    ///     .base_offset(0x4000_2000);
+    /// assert_eq!(format!("{:?}", Buf([0u8; 32]).display_with(&config)), concat!(
+    ///     "\n40002000: | 00 00 00 00 00 00 00 00 | 00 00 00 00 00 00 00 00 | ................",
+    ///     "\n40002010: | 00 00 00 00 00 00 00 00 | 00 00 00 00 00 00 00 00 | ................",
+    /// ));
+    /// ```
+    pub const fn base_offset(mut self, base: usize) -> Self {
+        self.base_offset = base;
+        self
+    }
+
+    /// Indents every line after the first by `spaces`, e.g. to align the
+    /// dump under a field name in hand written Debug output.
+    ///
+    /// The width of the format string, `{:8?}`, takes precedence.
+    ///
+    /// ```rust
+    /// use core::fmt;
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// struct Packet {
+    ///     payload: Buf<40>,
+    /// }
+    ///
+    /// impl fmt::Debug for Packet {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         let config = DumpConfig::portable().bytes_per_line(16).indent(9);
+    ///         write!(f, "payload: {:?}", self.payload.display_with(&config))
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(format!("{:?}", Packet { payload: Buf([0x55; 40]) }), concat!(
+    ///     "payload: ",
+    ///     "\n          | 55 55 55 55 55 55 55 55 | 55 55 55 55 55 55 55 55 | UUUUUUUUUUUUUUUU",
+    ///     "\n          | 55 55 55 55 55 55 55 55 | 55 55 55 55 55 55 55 55 | UUUUUUUUUUUUUUUU",
+    ///     "\n          | 55 55 55 55 55 55 55 55                           | UUUUUUUU",
+    /// ));
+    /// ```
+    pub const fn indent(mut self, spaces: usize) -> Self {
+        self.indent = spaces;
+        self
+    }
+
+    /// Prefixes hexadecimal offsets and addresses with `0x`, a shorthand for
+    /// [`offset_notation`](Self::offset_notation) with [`Notation::Prefix`].
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .offsets(Offsets::Hex(4))
+    ///     .base_offset(0xFF0)
+    ///     .offset_prefix(true)
+    ///     .offset_uppercase(false);
+    /// let buf = Buf(*b"matches the firmware log");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n0x0ff0: | 6D 61 74 63 68 65 73 20 | 74 68 65 20 66 69 72 6D | matches.the.firm",
+    ///     "\n0x1000: | 77 61 72 65 20 6C 6F 67                           | ware.log",
+    /// ));
+    /// ```
+    pub const fn offset_prefix(mut self, enabled: bool) -> Self {
+        self.offset_notation = if enabled {
+            Notation::Prefix
+        } else {
+            Notation::Plain
+        };
+        self
+    }
+
+    /// Marks hexadecimal offsets and addresses as `0x1F0` or `1F0h`.
+    pub const fn offset_notation(mut self, notation: Notation) -> Self {
+        self.offset_notation = notation;
+        self
+    }
+
+    /// Marks the hexadecimal words as `0xAB` or `ABh`, e.g. to paste them
+    /// into C code or assembler listings. Combine it with
+    /// [`uppercase`](Self::uppercase) for `0xab`.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Notation};
+    ///
+    /// let config = DumpConfig::portable().notation(Notation::Prefix).uppercase(false);
+    /// let dump = format!("{:?}", Buf([0xAB, 0x01]).display_with(&config));
+    /// assert!(dump.starts_with("\n | 0xab 0x01 "));
+    /// ```
+    pub const fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Appends a line with the length, the number of zero bytes and the
+    /// offsets of the first and last non-zero byte.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let mut buf = Buf([0; 32]);
+    /// buf.0[3..7].copy_from_slice(b"data");
+    /// let config = DumpConfig::portable().bytes_per_line(16).summary(true);
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n | 00 00 00 64 61 74 61 00 | 00 00 00 00 00 00 00 00 | ...data.........",
+    ///     "\n | 00 00 00 00 00 00 00 00 | 00 00 00 00 00 00 00 00 | ................",
+    ///     "\n32 bytes, 28 zero, non-zero 0x03..=0x06",
+    /// ));
+    /// ```
+    pub const fn summary(mut self, enabled: bool) -> Self {
+        self.summary = enabled;
+        self
+    }
+
+    /// Writes the lines from the end to the start, e.g. to show the highest
+    /// address of a downward growing stack first.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let stack = Buf(*b"locals of the callee, return addr");
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(8)
+    ///     .offsets(Offsets::Hex(2))
+    ///     .reverse(true);
+    /// assert_eq!(format!("{:?}", stack.display_with(&config)), concat!(
+    ///     "\n20: | 72                      | r",
+    ///     "\n18: | 74 75 72 6E 20 61 64 64 | turn.add",
+    ///     "\n10: | 6C 6C 65 65 2C 20 72 65 | llee,.re",
+    ///     "\n08: | 66 20 74 68 65 20 63 61 | f.the.ca",
+    ///     "\n00: | 6C 6F 63 61 6C 73 20 6F | locals.o",
+    /// ));
+    ///
+    /// let mut stack = Buf([0; 48]);
+    /// stack.0[40..].copy_from_slice(b"ret addr");
+    /// let config = config.squeeze(true);
+    /// assert_eq!(format!("{:?}", stack.display_with(&config)), concat!(
+    ///     "\n28: | 72 65 74 20 61 64 64 72 | ret.addr",
+    ///     "\n20: | 00 00 00 00 00 00 00 00 | ........",
+    ///     "\n* 4 identical lines",
+    /// ));
+    /// assert_eq!(format!("{:?}", stack.display_with(&config.preview(1))), concat!(
+    ///     "\n28: | 72 65 74 20 61 64 64 72 | ret.addr",
+    ///     "\n… 32 bytes omitted …",
+    ///     "\n00: | 00 00 00 00 00 00 00 00 | ........",
+    /// ));
+    /// ```
+    pub const fn reverse(mut self, enabled: bool) -> Self {
+        self.reverse = enabled;
+        self
+    }
+
+    /// Right-aligns a partial last line with the end of the line instead of
+    /// its start, like several hex editors. The offset column still shows
+    /// the offset of its first byte.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(8)
+    ///     .group_size(4)
+    ///     .offsets(Offsets::Hex(2))
+    ///     .right_align_last(true);
+    /// let dump = format!("{:?}", Buf(*b"0123456789").display_with(&config));
+    /// assert_eq!(dump.lines().last(), Some("08: |             |       38 39 |       89"));
+    /// ```
+    pub const fn right_align_last(mut self, enabled: bool) -> Self {
+        self.right_align_last = enabled;
+        self
+    }
+
+    /// Chooses between uppercase and lowercase hexadecimal offsets and
+    /// addresses, independent of the case of the bytes.
+    ///
+    /// By default offsets follow [`uppercase`](Self::uppercase).
+    pub const fn offset_uppercase(mut self, enabled: bool) -> Self {
+        self.offset_uppercase = Some(enabled);
+        self
+    }
+
+    /// Enables or disables the column of hex digits.
+    ///
+    /// Without it, the dump shows only the printable characters at their
+    /// offsets, like a positional `strings`:
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .offsets(Offsets::Hex(4))
+    ///     .hex_column(false);
+    /// let buf = Buf(*b"\0\0only the text\xFF\xFFcolumn");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n0000: | ..only.the.text.",
+    ///     "\n0010: | .column",
+    /// ));
+    /// ```
+    pub const fn hex_column(mut self, enabled: bool) -> Self {
+        self.hex_column = enabled;
+        self
+    }
+
+    /// Enables or disables the character column at the end of each line.
+    pub const fn text_column(mut self, enabled: bool) -> Self {
+        self.text_column = enabled;
+        self
+    }
+
+    /// Moves the character column in front of the hex column.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .offsets(Offsets::Hex(4))
+    ///     .text_first(true);
+    /// let buf = Buf(*b"the text column comes first");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n0000: the.text.column. | 74 68 65 20 74 65 78 74 | 20 63 6F 6C 75 6D 6E 20",
+    ///     "\n0010: comes.first      | 63 6F 6D 65 73 20 66 69 | 72 73 74",
+    /// ));
+    /// ```
+    pub const fn text_first(mut self, enabled: bool) -> Self {
+        self.text_first = enabled;
+        self
+    }
+
+    /// Adds a header line with the offset of each column within a line,
+    /// like many hex editors.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Offsets};
+    ///
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .offsets(Offsets::Hex(4))
+    ///     .header(true);
+    /// let buf = Buf(*b"columns with a header line");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "      | 00 01 02 03 04 05 06 07 | 08 09 0A 0B 0C 0D 0E 0F | 0123456789ABCDEF",
+    ///     "\n0000: | 63 6F 6C 75 6D 6E 73 20 | 77 69 74 68 20 61 20 68 | columns.with.a.h",
+    ///     "\n0010: | 65 61 64 65 72 20 6C 69 | 6E 65                   | eader.line",
+    /// ));
+    /// ```
+    pub const fn header(mut self, enabled: bool) -> Self {
+        self.header = enabled;
+        self
+    }
+
+    /// Appends a checksum of the bytes to each line, to spot corrupted
+    /// lines in dumps copied from serial consoles.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Checksum, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(16).checksum(Checksum::Crc8);
+    /// let buf = Buf(*b"every line gets a checksum");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), concat!(
+    ///     "\n | 65 76 65 72 79 20 6C 69 | 6E 65 20 67 65 74 73 20 | every.line.gets. | 78",
+    ///     "\n | 61 20 63 68 65 63 6B 73 | 75 6D                   | a.checksum       | A1",
+    /// ));
+    /// ```
+    pub const fn checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Appends a column that interprets the bytes of each line as `values`,
+    /// read in the byte order set with [`endian`](Self::endian).
+    ///
+    /// Lines should be a multiple of the value size, a trailing partial
+    /// value is left out.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Endian, Values};
+    ///
+    /// let mut bytes = [0; 16];
+    /// for (chunk, value) in bytes.chunks_mut(4).zip([1.0f32, 0.0, f32::NAN, -2.5]) {
+    ///     chunk.copy_from_slice(&value.to_le_bytes());
+    /// }
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(16)
+    ///     .endian(Endian::Little)
+    ///     .values(Values::F32);
+    /// assert_eq!(format!("{:?}", Buf(bytes).display_with(&config)), concat!(
+    ///     "\n | 00 00 80 3F 00 00 00 00 | 00 00 C0 7F 00 00 20 C0 |",
+    ///     " ...?............ | 1.0, 0.0, NaN, -2.5",
+    /// ));
+    ///
+    /// let counters = Buf(*b"\x01\x00\xFF\xFF\x00\x80");
+    /// let config = DumpConfig::portable()
+    ///     .bytes_per_line(6)
+    ///     .endian(Endian::Little)
+    ///     .values(Values::I16);
+    /// assert_eq!(format!("{:?}", counters.display_with(&config)), "\n | 01 00 FF FF 00 80 | ...... | 1, -1, -32768");
+    /// ```
+    pub const fn values(mut self, values: Values) -> Self {
+        self.values = Some(values);
+        self
+    }
+
+    /// Sets how the character column decodes bytes, [`Charset::Ascii`] by default.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Charset, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(9).group_size(9);
+    /// let config = config.charset(Charset::Utf8);
+    /// let buf = Buf(*b"caf\xC3\xA9 \xE2\x9C\x93");
+    /// assert_eq!(format!("{:?}", buf.display_with(&config)), "\n | 63 61 66 C3 A9 20 E2 9C 93 | café·.✓··");
+    /// ```
+    pub const fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Sets the marker displayed for continuation bytes of multi byte
+    /// characters, `·` by default.
+    pub const fn continuation_placeholder(mut self, placeholder: char) -> Self {
+        self.continuation_placeholder = placeholder;
+        self
+    }
+
+    /// Sets the character displayed in the character column for bytes that
+    /// are not printable, `.` by default.
+    pub const fn placeholder(mut self, placeholder: char) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// Sets a distinct character displayed in the character column for
+    /// `0x00`, the [`placeholder`](Self::placeholder) by default.
+    pub const fn null_placeholder(mut self, placeholder: char) -> Self {
+        self.null_placeholder = Some(placeholder);
+        self
+    }
+
+    /// Displays `0x0A` as `␊` and `0x0D` as `␍` in the character column
+    /// with any [`charset`](Self::charset), to tell line endings apart.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().line_endings(true);
+    /// let dump = format!("{:?}", Buf(*b"200 OK\r\nok\n").display_with(&config));
+    /// assert!(dump.contains("200.OK␍␊ok␊"));
+    /// ```
+    pub const fn line_endings(mut self, enabled: bool) -> Self {
+        self.line_endings = enabled;
+        self
+    }
+
+    /// Enables or disables ANSI colors, disabled by default.
+    ///
+    /// Zero bytes are dimmed, printable ASCII, control characters and bytes
+    /// above `0x7F` have distinct colors in both columns. Colors stay off
+    /// unless enabled here, so a crate enabling the `color` feature does not
+    /// put escape codes into the dumps of every other crate.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"\0A");
+    /// assert!(!format!("{:?}", buf.display_with(&DumpConfig::new())).contains('\x1B'));
+    /// let colored = format!("{:?}", buf.display_with(&DumpConfig::new().color(true)));
+    /// assert!(colored.contains("\x1B[2m00\x1B[0m"));
+    /// ```
+    #[cfg(feature = "color")]
+    pub const fn color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Appends a line naming the color classes in their colors, for readers
+    /// unfamiliar with the colors.
+    #[cfg(feature = "color")]
+    pub const fn color_legend(mut self, enabled: bool) -> Self {
+        self.color_legend = enabled;
+        self
+    }
+
+    /// Shades the background of every other line or group to make wide
+    /// dumps easier to scan. The byte colors are kept on top of the shading.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Shading};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(2).group_size(2);
+    /// let config = config.color(true).shading(Shading::Lines);
+    /// let dump = format!("{:?}", Buf(*b"ZZZZ").display_with(&config));
+    /// let shade = "\x1B[48;5;236m";
+    /// assert!(!dump.lines().nth(1).unwrap().contains(shade));
+    /// assert!(dump.lines().nth(2).unwrap().starts_with(shade));
+    /// ```
+    #[cfg(feature = "color")]
+    pub const fn shading(mut self, shading: Shading) -> Self {
+        self.shading = shading;
+        self
+    }
+
+    /// Collapses runs of identical lines into a single line followed by a
+    /// `*` marker with the number of omitted lines, like `xxd` and `hexdump`.
+    pub const fn squeeze(mut self, enabled: bool) -> Self {
+        self.squeeze = enabled;
+        self
+    }
+
+    /// Keeps the last line of each block collapsed by [`squeeze`](Self::squeeze),
+    /// so the boundaries of fill patterns stay visible.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let mut flash = Buf([0xFF; 128]);
+    /// flash.0[..4].copy_from_slice(b"BOOT");
+    /// let config = DumpConfig::portable().bytes_per_line(16).squeeze(true).squeeze_context(true);
+    /// assert_eq!(format!("{:?}", flash.display_with(&config)), concat!(
+    ///     "\n | 42 4F 4F 54 FF FF FF FF | FF FF FF FF FF FF FF FF | BOOT............",
+    ///     "\n | FF FF FF FF FF FF FF FF | FF FF FF FF FF FF FF FF | ................",
+    ///     "\n* 5 identical lines",
+    ///     "\n | FF FF FF FF FF FF FF FF | FF FF FF FF FF FF FF FF | ................",
+    /// ));
+    /// ```
+    pub const fn squeeze_context(mut self, enabled: bool) -> Self {
+        self.squeeze_context = enabled;
+        self
+    }
+
+    /// Displays only the first and last `lines` lines of large buffers,
+    /// separated by the number of omitted bytes.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let dma = Buf([0xAA; 64 * 1024]);
+    /// let config = DumpConfig::portable().bytes_per_line(8).preview(2);
+    /// assert_eq!(format!("{:?}", dma.display_with(&config)), concat!(
+    ///     "\n | AA AA AA AA AA AA AA AA | ........",
+    ///     "\n | AA AA AA AA AA AA AA AA | ........",
+    ///     "\n… 65504 bytes omitted …",
+    ///     "\n | AA AA AA AA AA AA AA AA | ........",
+    ///     "\n | AA AA AA AA AA AA AA AA | ........",
+    /// ));
+    /// ```
+    pub const fn preview(mut self, lines: usize) -> Self {
+        self.preview = Some(lines);
+        self
+    }
+
+    /// Replaces runs of at least `threshold` zero bytes with `00 ×len`,
+    /// within a line as well as across lines of zeros.
+    ///
+    /// Runs within a line keep the width of the bytes they replace, so the
+    /// columns stay aligned.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let mut sparse = Buf([0; 256]);
+    /// sparse.0[2] = 0x17;
+    /// sparse.0[250] = 0x2A;
+    /// let config = DumpConfig::portable().bytes_per_line(16).zero_runs(8);
+    /// assert_eq!(format!("{:?}", sparse.display_with(&config)), concat!(
+    ///     "\n | 00 00 17 00 ×13                                   | ................",
+    ///     "\n | 00 ×224",
+    ///     "\n | 00 ×10                          2A 00 00 00 00 00 | ..........*.....",
+    /// ));
+    /// ```
+    pub const fn zero_runs(mut self, threshold: usize) -> Self {
+        self.zero_runs = Some(threshold);
+        self
+    }
+
+    /// Writes a marker line like `-- 0x1000 --` between the lines at every
+    /// multiple of `stride`, e.g. 64 for cache lines or 4096 for pages.
+    ///
+    /// Boundaries are at addresses with [`DumpConfig::address_alignment`],
+    /// otherwise at offsets from [`DumpConfig::base_offset`]. Squeezed lines
+    /// hide their boundaries.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(16).boundaries(64);
+    /// let dump = format!("{:?}", Buf([0x5A; 128]).display_with(&config));
+    /// assert_eq!(dump.lines().nth(5), Some("-- 0x40 --"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is 0.
+    pub const fn boundaries(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "boundary stride must not be 0");
+        self.boundaries = Some(stride);
+        self
+    }
+
+    /// Chooses between uppercase (the default) and lowercase hex digits.
+    ///
+    /// The `{:x?}` and `{:X?}` format specifiers have no stable accessor
+    /// and do not change the case, this setting does.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"\xAB\xCD");
+    /// let lower = buf.display_with(&DumpConfig::portable().bytes_per_line(2).uppercase(false));
+    /// assert_eq!(format!("{lower:?}"), "\n | ab cd | ..");
+    /// assert_eq!(format!("{lower:X?}"), "\n | ab cd | ..");
+    /// let upper = buf.display_with(&DumpConfig::portable().bytes_per_line(2));
+    /// assert_eq!(format!("{upper:x?}"), "\n | AB CD | ..");
+    /// ```
+    pub const fn uppercase(mut self, enabled: bool) -> Self {
+        self.uppercase = enabled;
+        self
+    }
+
+    /// Restricts the output to printable ASCII and `\n`, for serial
+    /// consoles and syslog transports that mangle anything else.
+    ///
+    /// Colors are disabled and any other character, e.g. of the
+    /// [`charset`](Self::charset) or a placeholder, is written as `.`.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Charset, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().charset(Charset::Utf8).plain(true);
+    /// let dump = format!("{:?}", Buf(*b"gr\xC3\xBC\xC3\x9Fe\t").display_with(&config));
+    /// assert!(dump.ends_with("| gr....e."));
+    /// assert!(dump.chars().all(|c| c == '\n' || (' '..='~').contains(&c)));
+    /// ```
+    pub const fn plain(mut self, enabled: bool) -> Self {
+        self.plain = enabled;
+        self
+    }
+
+    /// Draws a box of Unicode box-drawing characters around the lines of the
+    /// dump, for screenshots in documentation and bug reports.
+    ///
+    /// The columns are separated by `│`, overriding the
+    /// [group](Self::group_separator) and [column](Self::column_separator)
+    /// separators. The [title](crate::Dump::title) stays above the box, the
+    /// [summary](Self::summary) and the legend below it. The borders span
+    /// the full [`bytes_per_line`](Self::bytes_per_line).
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(4).box_drawing(true);
+    /// let dump = format!("{:?}", Buf(*b"memdbg").display_with(&config));
+    /// assert_eq!(dump, concat!(
+    ///     "\n",
+    ///     " ┌─────────────┬──────┐\n",
+    ///     " │ 6D 65 6D 64 │ memd │\n",
+    ///     " │ 62 67       │ bg   │\n",
+    ///     " └─────────────┴──────┘",
+    /// ));
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(300).box_drawing(true);
+    /// let dump = format!("{:?}", Buf([0; 300]).display_with(&config));
+    /// let widths: Vec<_> = dump.lines().skip(1).map(|line| line.chars().count()).collect();
+    /// assert_eq!(widths, [1281; 3]);
+    /// ```
+    pub const fn box_drawing(mut self, enabled: bool) -> Self {
+        self.box_drawing = enabled;
+        self
+    }
+}
+
+impl Default for DumpConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,111 @@
+//! CSV output for spreadsheets.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{Dump, DumpFormat, Formatted, Row};
+
+/// A hex dump as CSV, a row per line with its offset, a column per byte and
+/// the characters.
+///
+/// Created by [`Dump::csv`]. The characters are quoted, a leading `=`, `+`,
+/// `-` or `@` is escaped with `'` so spreadsheets do not evaluate them as
+/// a formula. Each row ends with a line break.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig};
+///
+/// let buf = Buf(*b"=1+2,\"x\"");
+/// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(4));
+/// assert_eq!(format!("{}", dump.csv()), concat!(
+///     "offset,b0,b1,b2,b3,ascii\n",
+///     "0x0000,3D,31,2B,32,\"'=1+2\"\n",
+///     "0x0004,2C,22,78,22,\",\"\"x\"\"\"\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Csv<'a> {
+    dump: Dump<'a>,
+}
+
+impl<'a> Csv<'a> {
+    pub(crate) fn new(dump: Dump<'a>) -> Self {
+        Self { dump }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let config = self.dump.config();
+        let rows = Rows {
+            bytes_per_line: config.bytes_per_line,
+            uppercase: config.uppercase,
+        };
+        Display::fmt(&Formatted::new(self.dump, rows), f)
+    }
+}
+
+impl Debug for Csv<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Csv::fmt(self, f)
+    }
+}
+
+impl Display for Csv<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Csv::fmt(self, f)
+    }
+}
+
+/// Writes the rows of a [`Csv`].
+struct Rows {
+    bytes_per_line: usize,
+    uppercase: bool,
+}
+
+impl DumpFormat for Rows {
+    fn begin(&self, f: &mut dyn Write, bytes_per_line: usize) -> core::fmt::Result {
+        f.write_str("offset")?;
+        (0..bytes_per_line).try_for_each(|column| f.write_fmt(format_args!(",b{}", column)))?;
+        f.write_str(",ascii\n")
+    }
+
+    fn row(&self, f: &mut dyn Write, row: &Row<'_>) -> core::fmt::Result {
+        match self.uppercase {
+            true => f.write_fmt(format_args!("0x{:04X}", row.offset()))?,
+            false => f.write_fmt(format_args!("0x{:04x}", row.offset()))?,
+        }
+        for i in 0..self.bytes_per_line {
+            f.write_str(",")?;
+            if i < row.bytes().len() {
+                row.write_byte(f, i)?;
+            }
+        }
+        f.write_str(",\"")?;
+        row.write_text(&mut Quote {
+            inner: f,
+            start: true,
+        })?;
+        f.write_str("\"\n")
+    }
+}
+
+/// Escapes the text for a quoted field.
+struct Quote<'w, W: ?Sized> {
+    inner: &'w mut W,
+    /// Whether nothing was written yet.
+    start: bool,
+}
+
+impl<W: Write + ?Sized> Write for Quote<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            match char {
+                '=' | '+' | '-' | '@' if self.start => self.inner.write_str("'")?,
+                '"' => self.inner.write_str("\"")?,
+                _ => {}
+            }
+            self.start = false;
+            self.inner.write_char(char)?;
+        }
+
+        Ok(())
+    }
+}
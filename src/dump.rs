@@ -0,0 +1,1873 @@
+use core::{
+    cmp::{max, min},
+    fmt::{Alignment, Debug, Display, Formatter, Write},
+    ops::Range,
+};
+
+#[cfg(feature = "color")]
+use crate::color::{self, Class};
+use crate::{text::Cell, Checksum, DumpConfig, Highlight, Offsets, Radix, Region, Values};
+#[cfg(feature = "color")]
+use crate::{Shading, Style};
+
+/// The buffer passed to [`Dump::render_into`] cannot hold the dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TooSmall {
+    /// The length of the dump.
+    pub needed: usize,
+}
+
+impl Display for TooSmall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "the dump needs a buffer of {} bytes",
+            self.needed
+        ))
+    }
+}
+
+/// Writes custom metadata in front of the line at the given offset, see [`Dump::prefix_with`].
+pub type LinePrefix<'a> = dyn Fn(&mut dyn Write, usize) -> core::fmt::Result + 'a;
+
+/// A hex dump of a byte slice using a custom [`DumpConfig`].
+///
+/// Created by [`Buf::display_with`](crate::Buf::display_with).
+#[derive(Clone, Copy)]
+pub struct Dump<'a> {
+    bytes: &'a [u8],
+    config: DumpConfig,
+    highlights: &'a [Highlight],
+    regions: &'a [Region<'a>],
+    padding: &'a [bool],
+    /// Written for each digit of a byte masked by `padding`.
+    padding_marker: &'static str,
+    redacted: &'a [Range<usize>],
+    cursors: &'a [usize],
+    title: Option<&'a str>,
+    prefix: Option<&'a LinePrefix<'a>>,
+    /// The address used for alignment and [`Offsets::Address`].
+    addr: usize,
+}
+
+impl<'a> Dump<'a> {
+    pub(crate) fn new(bytes: &'a [u8], config: &DumpConfig) -> Self {
+        Self {
+            bytes,
+            config: *config,
+            highlights: &[],
+            regions: &[],
+            padding: &[],
+            padding_marker: "-",
+            redacted: &[],
+            cursors: &[],
+            title: None,
+            prefix: None,
+            addr: bytes.as_ptr() as usize,
+        }
+    }
+
+    /// Uses `addr` instead of the address of the bytes, for dumps of copies.
+    pub(crate) fn with_addr(mut self, addr: usize) -> Self {
+        self.addr = addr;
+        self
+    }
+
+    /// Marks byte ranges in both columns, e.g. to point at the bytes that are wrong.
+    ///
+    /// If highlights overlap, the first one wins. Without
+    /// [colors](DumpConfig::color) every style encloses the bytes in brackets.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Highlight, Style};
+    ///
+    /// let buf = Buf(*b"magic: \xDE\xAD\xBE\xEF, rest of the packet");
+    /// let config = DumpConfig::portable().bytes_per_line(16);
+    /// let highlights = [Highlight::new(7..11, Style::Brackets)];
+    /// assert_eq!(format!("{:?}", buf.display_with(&config).highlight(&highlights)), concat!(
+    ///     "\n | 6D 61 67 69 63 3A 20[DE | AD BE EF]2C 20 72 65 73 | magic:.[....],.res",
+    ///     "\n | 74 20 6F 66 20 74 68 65 | 20 70 61 63 6B 65 74    | t.of.the.packet",
+    /// ));
+    /// ```
+    pub fn highlight(mut self, highlights: &'a [Highlight]) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Encloses named regions in brackets and lists their labels after the dump.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Region};
+    ///
+    /// let buf = Buf(*b"\x7FELF\x02\x01\x01\x00");
+    /// let regions = [
+    ///     Region::new(0, 4, "header.magic"),
+    ///     Region::new(4, 1, "header.class"),
+    ///     Region::new(5, 1, "header.data"),
+    /// ];
+    /// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(8));
+    /// assert_eq!(format!("{:?}", dump.annotate(&regions)), concat!(
+    ///     "\n |[7F 45 4C 46|02|01]01 00 | [.ELF][.][.]..",
+    ///     "\n[0x00..0x04) header.magic",
+    ///     "\n[0x04..0x05) header.class",
+    ///     "\n[0x05..0x06) header.data",
+    /// ));
+    /// ```
+    pub fn annotate(mut self, regions: &'a [Region<'a>]) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Displays the bytes marked in `mask` as `--`, e.g. the padding bytes of a struct.
+    ///
+    /// Masked bytes are blank in the character column. Bytes past the end of
+    /// the mask are not masked.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// // a `#[repr(C)] struct { a: u8, b: u32 }`
+    /// let buf = Buf([1, 0, 0, 0, 2, 0, 0, 0]);
+    /// let padding = [false, true, true, true];
+    /// let config = DumpConfig::portable().bytes_per_line(8);
+    /// let dump = buf.display_with(&config).padding(&padding);
+    /// assert_eq!(format!("{:?}", dump), "\n | 01 -- -- -- 02 00 00 00 | .   ....");
+    /// ```
+    pub fn padding(mut self, mask: &'a [bool]) -> Self {
+        self.padding = mask;
+        self
+    }
+
+    /// Displays the bytes marked in `mask` as `??` instead, for bytes that
+    /// are uninitialized and were never read.
+    pub(crate) fn uninit(mut self, mask: &'a [bool]) -> Self {
+        self.padding = mask;
+        self.padding_marker = "?";
+        self
+    }
+
+    /// Hides the bytes in `ranges`, they are displayed as `**` and `*` in
+    /// the character column, e.g. to share dumps that contain keys.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let packet = Buf(*b"KEY:\x13\x37\xC0\xDE\xBA\xBE;rest");
+    /// let dump = format!("{:?}", packet.display_with(&DumpConfig::portable()).redact(&[4..10]));
+    /// assert!(!dump.contains("13") && dump.contains("KEY:******;rest"));
+    /// ```
+    pub fn redact(mut self, ranges: &'a [Range<usize>]) -> Self {
+        self.redacted = ranges;
+        self
+    }
+
+    /// Prints a line of carets beneath the bytes at the `offsets` in both
+    /// columns, like compiler diagnostics, e.g. to point at a parse error.
+    ///
+    /// Words larger than a byte are marked as a whole.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let request = Buf(*b"GET /index.html\x7FHTTP/1.1");
+    /// let config = DumpConfig::portable().bytes_per_line(16);
+    /// let dump = format!("{:?}", request.display_with(&config).cursors(&[15]));
+    /// assert_eq!(dump.lines().nth(2), Some(concat!(
+    ///     "                                                  ^^",
+    ///     "                  ^",
+    /// )));
+    /// ```
+    pub fn cursors(mut self, offsets: &'a [usize]) -> Self {
+        self.cursors = offsets;
+        self
+    }
+
+    /// Prints `title` and the length of the dump above it, to tell dumps
+    /// apart in a log.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(4);
+    /// let rx = [Buf([0x7E; 4]), Buf([0x55; 4])];
+    /// for (buf, row) in rx.iter().zip(["| 7E 7E 7E 7E | ~~~~", "| 55 55 55 55 | UUUU"]) {
+    ///     let dump = buf.display_with(&config).title("rx_dma[3]");
+    ///     assert_eq!(format!("{:?}", dump), format!("rx_dma[3] (4 bytes)\n {}", row));
+    /// }
+    /// ```
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    /// Calls `prefix` with the offset of the first byte of each line to
+    /// write custom metadata in front of it, e.g. bank numbers or cache tags.
+    ///
+    /// ```rust
+    /// use core::fmt::Write;
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let bank = |f: &mut dyn Write, offset: usize| write!(f, "bank {}", offset / 16);
+    /// let config = DumpConfig::portable().bytes_per_line(8);
+    /// let buf = Buf([0x5A; 32]);
+    /// assert_eq!(format!("{:?}", buf.display_with(&config).prefix_with(&bank)), concat!(
+    ///     "\nbank 0 | 5A 5A 5A 5A 5A 5A 5A 5A | ZZZZZZZZ",
+    ///     "\nbank 0 | 5A 5A 5A 5A 5A 5A 5A 5A | ZZZZZZZZ",
+    ///     "\nbank 1 | 5A 5A 5A 5A 5A 5A 5A 5A | ZZZZZZZZ",
+    ///     "\nbank 1 | 5A 5A 5A 5A 5A 5A 5A 5A | ZZZZZZZZ",
+    /// ));
+    /// ```
+    ///
+    /// The dump is written in a single pass. `prefix` is called once for
+    /// each line and once more to measure it with the
+    /// [header](DumpConfig::header), the [box](DumpConfig::box_drawing) or
+    /// the carets of the [cursors](Dump::cursors) of a line.
+    ///
+    /// ```rust
+    /// use core::{cell::Cell, fmt::Write};
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let calls = Cell::new(0);
+    /// let count = |f: &mut dyn Write, _: usize| {
+    ///     calls.set(calls.get() + 1);
+    ///     f.write_str(">")
+    /// };
+    /// let config = DumpConfig::portable().bytes_per_line(8).box_drawing(true);
+    /// let dump = format!("{:?}", Buf([0x5A; 16]).display_with(&config).prefix_with(&count));
+    /// assert_eq!(dump.lines().nth(1), Some("  ┌─────────────────────────┬──────────┐"));
+    /// assert_eq!(calls.get(), 3);
+    /// ```
+    pub fn prefix_with(mut self, prefix: &'a LinePrefix<'a>) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Renders the dump as HTML, with classes instead of colors.
+    #[cfg(feature = "html")]
+    pub fn html(self) -> crate::Html<'a> {
+        crate::Html::new(Self {
+            config: self.config.color(true),
+            ..self
+        })
+    }
+
+    /// Renders the dump in a Markdown code block or table, without colors.
+    pub fn markdown(self) -> crate::Markdown<'a> {
+        crate::Markdown::new(Self {
+            config: DumpConfig {
+                canonical: true,
+                ..self.colorless()
+            },
+            ..self
+        })
+    }
+
+    /// Renders the dump as a YAML literal block, in printable ASCII.
+    pub fn yaml(self) -> crate::Yaml<'a> {
+        crate::Yaml::new(Self {
+            config: DumpConfig {
+                plain: true,
+                canonical: true,
+                ..self.config
+            },
+            ..self
+        })
+    }
+
+    /// Renders the dump as CSV for spreadsheets, without colors.
+    pub fn csv(self) -> crate::Csv<'a> {
+        crate::Csv::new(Self {
+            config: DumpConfig {
+                address_alignment: false,
+                squeeze: false,
+                ..self.colorless()
+            },
+            ..self
+        })
+    }
+
+    /// Writes the rows of the dump in a custom format, without colors.
+    ///
+    /// See [`DumpFormat`](crate::DumpFormat).
+    pub fn format<F: crate::DumpFormat>(self, format: F) -> crate::Formatted<'a, F> {
+        crate::Formatted::new(
+            Self {
+                config: self.colorless(),
+                ..self
+            },
+            format,
+        )
+    }
+
+    /// Renders the dump as JSON for log pipelines, an object per line with
+    /// its offset, bytes, characters and annotations.
+    #[cfg(feature = "json")]
+    pub fn json(self) -> crate::Json<'a> {
+        let dump = Self {
+            config: DumpConfig {
+                radix: Radix::Hex,
+                notation: crate::Notation::Plain,
+                ..self.colorless()
+            },
+            highlights: &[],
+            regions: &[],
+            ..self
+        };
+        crate::Json::new(dump, self.regions)
+    }
+
+    /// Writes the regions of [`Dump::annotate`] with their bytes as JSON.
+    #[cfg(feature = "json")]
+    pub fn annotations_json(self) -> crate::Annotations<'a> {
+        crate::Annotations::new(self.json())
+    }
+
+    /// The config without colors, for formats without ANSI escape codes.
+    fn colorless(&self) -> DumpConfig {
+        #[cfg(feature = "color")]
+        return self.config.color(false);
+        #[cfg(not(feature = "color"))]
+        self.config
+    }
+
+    pub(crate) fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub(crate) fn config(&self) -> &DumpConfig {
+        &self.config
+    }
+
+    /// Writes the dump, honoring the flags of the formatter.
+    ///
+    /// The precision limits the number of bytes, the width indents every
+    /// line with the fill character instead of [`DumpConfig::indent`].
+    pub(crate) fn fmt_table(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let dump = self.truncated(f.precision());
+        let (fill, width) = match f.width() {
+            Some(width) => (f.fill(), width),
+            None => (' ', dump.config.indent),
+        };
+        if width == 0 {
+            return dump.write(f);
+        }
+        dump.write(&mut Indent {
+            inner: f,
+            fill,
+            width,
+        })
+    }
+
+    /// Writes the dump into `w` without a [`Formatter`], e.g. into a
+    /// fixed-capacity string or straight to a UART, as `{:?}` displays it.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"uart");
+    /// let dump = buf.display_with(&DumpConfig::portable());
+    /// let mut out = String::new();
+    /// dump.write_to(&mut out)?;
+    /// assert_eq!(out, format!("{:?}", dump));
+    /// # Ok::<(), core::fmt::Error>(())
+    /// ```
+    pub fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        let width = self.config.indent;
+        if width == 0 {
+            return self.write(w);
+        }
+        self.write(&mut Indent {
+            inner: w,
+            fill: ' ',
+            width,
+        })
+    }
+
+    /// Writes the dump as UTF-8 into `out` and returns its length, for
+    /// targets without a [`Formatter`] or an allocator at hand.
+    ///
+    /// If `out` is too small, it holds the start of the dump and the error
+    /// the length of the full dump.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, TooSmall};
+    ///
+    /// let buf = Buf(*b"stack");
+    /// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(8));
+    /// let mut out = [0; 64];
+    /// let len = dump.render_into(&mut out)?;
+    /// assert_eq!(&out[..len], b"\n | 73 74 61 63 6B          | stack");
+    /// assert_eq!(dump.render_into(&mut out[..8]), Err(TooSmall { needed: len }));
+    /// # Ok::<(), TooSmall>(())
+    /// ```
+    pub fn render_into(&self, out: &mut [u8]) -> Result<usize, TooSmall> {
+        let mut slice = Slice { out, len: 0 };
+        let _ = self.write_to(&mut slice);
+        match slice.len <= slice.out.len() {
+            true => Ok(slice.len),
+            false => Err(TooSmall { needed: slice.len }),
+        }
+    }
+
+    /// Writes all bytes space separated on a single line, honoring the flags of the formatter.
+    ///
+    /// The precision limits the number of bytes, the width pads the line
+    /// like a string.
+    pub(crate) fn fmt_compact(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let dump = self.truncated(f.precision());
+        let Some(width) = f.width() else {
+            return dump.write_compact(f);
+        };
+
+        let mut len = Width(0);
+        dump.write_compact(&mut len)?;
+        let padding = width.saturating_sub(len.0);
+        let (before, after) = match f.align() {
+            None | Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Right) => (padding, 0),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        };
+        let fill = f.fill();
+        (0..before).try_for_each(|_| f.write_char(fill))?;
+        dump.write_compact(f)?;
+        (0..after).try_for_each(|_| f.write_char(fill))
+    }
+
+    /// The first `len` bytes of the dump, all of them for `None`.
+    fn truncated(&self, len: Option<usize>) -> Self {
+        let len = min(len.unwrap_or(usize::MAX), self.bytes.len());
+        Self {
+            bytes: &self.bytes[..len],
+            ..*self
+        }
+    }
+
+    fn write_compact<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        for (i, &byte) in self.bytes.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            self.write_byte(f, i, byte)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single byte without colors, `--` for padding and `**` if redacted.
+    pub(crate) fn write_byte<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        index: usize,
+        byte: u8,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        if self.is_padding(index..index + 1) {
+            write_repeated(f, self.padding_marker, config.digits(1))
+        } else if self.is_redacted(index..index + 1) {
+            write_repeated(f, "*", config.digits(1))
+        } else {
+            let value = if config.nibble_swap {
+                swap_nibbles(byte.into())
+            } else {
+                byte.into()
+            };
+            write_digits(f, value, config.digits(1), config)
+        }
+    }
+
+    pub(crate) fn write<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        if self.config.plain {
+            let dump = Self {
+                config: DumpConfig {
+                    plain: false,
+                    ..self.colorless()
+                },
+                ..*self
+            };
+            let plain: &mut dyn Write = &mut Plain(f);
+            return dump.write(plain);
+        }
+        if self.config.canonical {
+            let dump = Self {
+                config: DumpConfig {
+                    canonical: false,
+                    ..self.config
+                },
+                ..*self
+            };
+            let canonical: &mut dyn Write = &mut Canonical {
+                inner: f,
+                started: false,
+                spaces: 0,
+            };
+            return dump.write(canonical);
+        }
+        if self.config.box_drawing {
+            let dump = Self {
+                config: DumpConfig {
+                    box_drawing: false,
+                    group_separator: "│",
+                    column_separator: "│",
+                    ..self.config
+                },
+                ..*self
+            };
+            // the prefix of the first line is measured once to place the borders
+            let mut lead = Width(0);
+            dump.write_prefix(&mut lead, 0)?;
+            let mut width = Width(0);
+            dump.write_layout(&mut width, lead.0)?;
+            // the first line of the box, the rows follow a line break unless
+            // the header or the unaligned bytes start the dump
+            let starts = self.config.header || (dump.unaligned() != 0 && !self.config.reverse);
+            let first = if self.title.is_none() && starts { 0 } else { 1 };
+            let mut boxed = Boxed {
+                inner: f,
+                dump: &dump,
+                first,
+                lead: lead.0,
+                width: width.0,
+                line: 0,
+                column: 0,
+                escape: false,
+            };
+            if first == 0 {
+                dump.write_border(boxed.inner, ['┌', '┬', '┐'], lead.0)?;
+                boxed.inner.write_str("\n")?;
+            }
+            let w: &mut dyn Write = &mut boxed;
+            dump.write_lines(w)?;
+            boxed.finish()?;
+            return dump.write_footer(f);
+        }
+
+        self.write_lines(f)?;
+        self.write_footer(f)
+    }
+
+    /// Writes the title, the header and the rows of the dump.
+    fn write_lines<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        let (buf, config) = (self.bytes, &self.config);
+        let line_len = config.bytes_per_line;
+        let addr = self.addr;
+        let (pre, rest) = buf.split_at(self.unaligned());
+        let lines = rest.len().div_ceil(line_len);
+        if let Some(title) = self.title {
+            match buf.len() {
+                1 => f.write_fmt(format_args!("{} (1 byte)", title))?,
+                len => f.write_fmt(format_args!("{} ({} bytes)", title, len))?,
+            }
+        }
+        if config.header {
+            if self.title.is_some() {
+                f.write_str("\n")?;
+            }
+            let last = pre.len() + lines.saturating_sub(1) * line_len;
+            let mut width = Width(0);
+            if let Some(prefix) = self.prefix {
+                prefix(&mut width, 0)?;
+            }
+            write_offset(&mut width, config, addr, last)?;
+            f.write_str(config.line_prefix)?;
+            self.write_header(f, width.0)?;
+            f.write_str(config.line_suffix)?;
+        }
+        if !pre.is_empty() && !config.reverse {
+            if config.header || self.title.is_some() {
+                f.write_str("\n")?;
+            }
+            self.write_unaligned(f, pre.len())?;
+        }
+        let omitted = match config.preview {
+            Some(preview) if lines > 2 * preview => preview..lines - preview,
+            _ => 0..0,
+        };
+        // the lines in the order they are written
+        let line_at = |k: usize| if config.reverse { lines - 1 - k } else { k };
+        let mut previous = None;
+        let mut squeezed = 0;
+        // the start of the last line written or omitted
+        let mut last = 0;
+        // the lowest start of the pending lines of zeros and their number
+        let mut zeros = (0, 0);
+        for k in 0..lines {
+            let i = line_at(k);
+            let start = pre.len() + i * line_len;
+            let line = &buf[start..min(start + line_len, buf.len())];
+            if omitted.contains(&k) {
+                if k == omitted.start {
+                    self.write_squeezed(f, squeezed, last)?;
+                    squeezed = 0;
+                    previous = None;
+                    self.write_zero_lines(f, zeros.0, zeros.1)?;
+                    zeros = (0, 0);
+                    let (first, end) = match config.reverse {
+                        false => (omitted.start, omitted.end),
+                        true => (lines - omitted.end, lines - omitted.start),
+                    };
+                    let bytes = min(end * line_len, rest.len()) - first * line_len;
+                    f.write_fmt(format_args!("\n… {} bytes omitted …", bytes))?;
+                }
+                continue;
+            }
+            if config.zero_runs.is_some()
+                && line.len() == line_len
+                && self.zero_run(start, start + line_len) == Some(start + line_len)
+            {
+                self.write_squeezed(f, squeezed, last)?;
+                squeezed = 0;
+                previous = None;
+                if zeros.1 == 0 || config.reverse {
+                    zeros.0 = start;
+                }
+                zeros.1 += 1;
+                continue;
+            }
+            self.write_zero_lines(f, zeros.0, zeros.1)?;
+            zeros = (0, 0);
+            if self.repeats(start..start + line.len(), previous) {
+                squeezed += 1;
+                last = start;
+                continue;
+            }
+            self.write_squeezed(f, squeezed, last)?;
+            squeezed = 0;
+            previous = Some(line);
+            last = start;
+
+            self.write_row(f, start..start + line.len())?;
+        }
+        self.write_squeezed(f, squeezed, last)?;
+        self.write_zero_lines(f, zeros.0, zeros.1)?;
+        if !pre.is_empty() && config.reverse {
+            f.write_str("\n")?;
+            self.write_unaligned(f, pre.len())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the summary and the legend below the rows of the dump.
+    fn write_footer<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        if self.config.summary {
+            self.write_summary(f)?;
+        }
+        self.write_legend(f)
+    }
+
+    /// Writes the `len` bytes before the first aligned address on a line of their own.
+    fn write_unaligned<W: Write + ?Sized>(&self, f: &mut W, len: usize) -> core::fmt::Result {
+        let config = &self.config;
+        self.write_prefix(f, 0)?;
+        let mut open = None;
+        if config.text_first && config.text_column {
+            f.write_str(" ")?;
+            self.write_text(f, 0..len)?;
+        }
+        if config.hex_column {
+            self.write_words(f, 0..len, &mut open)?;
+        }
+        if config.text_column && !config.text_first {
+            write_separator(f, open.is_some(), config.column_separator)?;
+            self.write_text(f, 0..len)?;
+        } else if open.is_some() {
+            f.write_str("]")?;
+        }
+        if let Some(checksum) = config.checksum.of(&self.bytes[..len]) {
+            write_column_separator(f, config, Some(0))?;
+            write_digits(f, checksum.into(), config.digits(1), config)?;
+        }
+        if let Some(values) = config.values {
+            write_column_separator(f, config, Some(0))?;
+            self.write_values(f, values, 0..len)?;
+        }
+        f.write_str(config.line_suffix)?;
+        self.write_carets(f, 0..len, true)
+    }
+
+    /// Writes a line of carets beneath the [cursors](Dump::cursors) in the
+    /// line of `range`, which is the line of unaligned bytes with `unaligned`.
+    fn write_carets<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+        unaligned: bool,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        let is_cursor = |i: &usize| self.cursors.contains(i);
+        if !range.clone().any(|i| is_cursor(&i)) {
+            return Ok(());
+        }
+
+        let mut prefix = Width(0);
+        self.write_prefix(&mut prefix, range.start)?;
+        let word_width = 1 + config.digits(config.word_size);
+        let separator = 1 + config.group_separator.chars().count();
+        let text_first = config.text_first && config.text_column;
+        // the unaligned line is neither grouped nor padded
+        let len = if unaligned {
+            range.len()
+        } else {
+            config.bytes_per_line
+        };
+        let hex_len = match unaligned {
+            _ if !config.hex_column => 0,
+            true => len.div_ceil(config.word_size) * word_width,
+            false => hex_width(len, config),
+        };
+        let lead = if unaligned { 0 } else { self.lead(range.len()) };
+        let hex_start = prefix.0 + if text_first { 1 + len } else { 0 };
+        let text_start = lead
+            + match text_first {
+                true => prefix.0 + 1,
+                false if unaligned => {
+                    prefix.0 + hex_len + 1 + config.column_separator.chars().count()
+                }
+                false => prefix.0 + hex_len + 2 + config.column_separator.chars().count(),
+            };
+
+        f.write_str("\n")?;
+        let mut column = 0;
+        for text in [text_first, !text_first] {
+            if !(if text {
+                config.text_column
+            } else {
+                config.hex_column
+            }) {
+                continue;
+            }
+            for i in range.clone().filter(is_cursor) {
+                let (at, width) = if text {
+                    (text_start + i - range.start, 1)
+                } else {
+                    let word = (lead + i - range.start) / config.word_size * config.word_size;
+                    let at = match unaligned {
+                        true => word / config.word_size * word_width,
+                        false if word % config.group_size == 0 => {
+                            hex_width(word, config) + separator
+                        }
+                        false => hex_width(word, config),
+                    };
+                    (hex_start + at + 1, config.digits(config.word_size))
+                };
+                // another cursor in the same word
+                if at < column {
+                    continue;
+                }
+                write_fill(f, at - column)?;
+                write_repeated(f, "^", width)?;
+                column = at + width;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the length, the number of zero bytes and the range of non-zero bytes.
+    fn write_summary<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        let buf = self.bytes;
+        let zeros = buf.iter().filter(|&&byte| byte == 0).count();
+        f.write_fmt(format_args!("\n{} bytes, {} zero", buf.len(), zeros))?;
+        let first = buf.iter().position(|&byte| byte != 0);
+        let last = buf.iter().rposition(|&byte| byte != 0);
+        let (Some(first), Some(last)) = (first, last) else {
+            return Ok(());
+        };
+        if self.config.uppercase {
+            f.write_fmt(format_args!(", non-zero {:#04X}..={:#04X}", first, last))
+        } else {
+            f.write_fmt(format_args!(", non-zero {:#04x}..={:#04x}", first, last))
+        }
+    }
+
+    /// Writes the `*` marker for `count` omitted identical lines, the last
+    /// of them starts at `last`.
+    ///
+    /// With [`DumpConfig::squeeze_context`] the last of them is written as well.
+    pub(crate) fn write_squeezed<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        mut count: usize,
+        last: usize,
+    ) -> core::fmt::Result {
+        let context = self.config.squeeze_context && count > 0;
+        if context {
+            count -= 1;
+        }
+        match count {
+            0 => {}
+            1 => f.write_str("\n* 1 identical line")?,
+            _ => f.write_fmt(format_args!("\n* {} identical lines", count))?,
+        }
+        if context {
+            self.write_row(f, last..last + self.config.bytes_per_line)?;
+        }
+
+        Ok(())
+    }
+
+    /// The number of bytes in front of the first aligned address, written
+    /// on a line of their own.
+    pub(crate) fn unaligned(&self) -> usize {
+        // groups start at addresses that are a multiple of their size
+        let offset = match self.config.address_alignment {
+            true => self.addr.wrapping_neg() % self.config.group_size,
+            false => 0,
+        };
+        min(offset, self.bytes.len())
+    }
+
+    /// The rows of the dump, the bytes in front of the first aligned address
+    /// followed by lines of [`DumpConfig::bytes_per_line`] bytes.
+    pub(crate) fn rows(&self) -> impl Iterator<Item = Range<usize>> {
+        let (len, line_len, pre) = (
+            self.bytes.len(),
+            self.config.bytes_per_line,
+            self.unaligned(),
+        );
+        let first = (pre != 0).then_some(0..pre);
+        let lines = (pre..len)
+            .step_by(line_len)
+            .map(move |start| start..min(start + line_len, len));
+        first.into_iter().chain(lines)
+    }
+
+    /// Whether [`DumpConfig::squeeze`] collapses the row of `range` since it
+    /// repeats the `previous` row.
+    pub(crate) fn repeats(&self, range: Range<usize>, previous: Option<&[u8]>) -> bool {
+        self.config.squeeze
+            && range.len() == self.config.bytes_per_line
+            && previous == Some(&self.bytes[range.clone()])
+            && !self.is_redacted(range)
+    }
+
+    /// Writes a line of the dump, including its offset.
+    pub(crate) fn write_row<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        if !config.reverse {
+            self.write_boundary(f, range.clone())?;
+        }
+        let shade = self.shade(range.start.div_ceil(config.bytes_per_line), false);
+        f.write_str("\n")?;
+        self.write_prefix(f, range.start)?;
+        write_shaded(f, shade, |f| self.write_line(f, range.clone()))?;
+        f.write_str(config.line_suffix)?;
+        self.write_carets(f, range.clone(), false)?;
+        if config.reverse {
+            self.write_boundary(f, range)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the separators of a full line and blanks in place of its
+    /// columns, after `lead` blanks for everything in front of the bytes,
+    /// to measure or draw around the lines without writing them.
+    fn write_layout<W: Write + ?Sized>(&self, f: &mut W, lead: usize) -> core::fmt::Result {
+        let config = &self.config;
+        let (len, group_size) = (config.bytes_per_line, config.group_size);
+        write_fill(f, lead)?;
+        if config.text_column && config.text_first {
+            write_fill(f, 1 + len)?;
+        }
+        let word_width = 1 + config.digits(config.word_size);
+        for start in (0..len).step_by(group_size).filter(|_| config.hex_column) {
+            write_separator(f, false, config.group_separator)?;
+            write_fill(
+                f,
+                min(group_size, len - start).div_ceil(config.word_size) * word_width,
+            )?;
+        }
+        let text_last = config.text_column && !config.text_first;
+        let checksum = config.checksum != Checksum::None;
+        if text_last || checksum || config.values.is_some() {
+            write_separator(f, false, config.column_separator)?;
+            f.write_str(" ")?;
+        }
+        let mut fill = None;
+        if text_last {
+            write_fill(f, len)?;
+            fill = Some(0);
+        }
+        if checksum {
+            write_column_separator(f, config, fill)?;
+            write_fill(f, config.digits(1))?;
+            fill = Some(0);
+        }
+        if let Some(values) = config.values {
+            write_column_separator(f, config, fill)?;
+            // the values of a line of zeros, `0` or `0.0` each
+            let count = len / values.size();
+            let zero = match values {
+                Values::F32 | Values::F64 => 3,
+                _ => 1,
+            };
+            write_fill(f, (count * (zero + 2)).saturating_sub(2))?;
+        }
+        f.write_str(config.line_suffix)
+    }
+
+    /// Writes the top or bottom border of the [box](DumpConfig::box_drawing)
+    /// with the `corners` at its left, at the column separators and at its right.
+    fn write_border<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        corners: [char; 3],
+        lead: usize,
+    ) -> core::fmt::Result {
+        self.write_layout(
+            &mut Border {
+                inner: f,
+                corners,
+                started: false,
+            },
+            lead,
+        )?;
+        f.write_char('─')?;
+        f.write_char(corners[2])
+    }
+
+    /// Writes the [boundary](DumpConfig::boundaries) marker in front of the
+    /// line of `range`, if a boundary falls within it.
+    ///
+    /// In reverse order the marker follows the line instead, so it stays
+    /// between the bytes on both sides of the boundary.
+    fn write_boundary<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        let Some(stride) = config.boundaries else {
+            return Ok(());
+        };
+        let start = match config.address_alignment {
+            true => self.addr.wrapping_add(range.start),
+            false => config.base_offset.wrapping_add(range.start),
+        };
+        let Some(boundary) = start.checked_next_multiple_of(stride) else {
+            return Ok(());
+        };
+        // there is nothing in front of the first byte to separate
+        if boundary - start >= range.len() || (boundary == start && range.start == 0) {
+            return Ok(());
+        }
+        if config.uppercase {
+            f.write_fmt(format_args!("\n-- {:#X} --", boundary))
+        } else {
+            f.write_fmt(format_args!("\n-- {:#x} --", boundary))
+        }
+    }
+
+    /// Writes everything in front of the line at `offset`, up to the offset column.
+    pub(crate) fn write_prefix<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        offset: usize,
+    ) -> core::fmt::Result {
+        f.write_str(self.config.line_prefix)?;
+        if let Some(prefix) = self.prefix {
+            prefix(&mut DynWrite(f), offset)?;
+        }
+        write_offset(f, &self.config, self.addr, offset)
+    }
+
+    /// Writes `count` lines of zeros from `start` on in the configured order, as a single
+    /// [zero run](DumpConfig::zero_runs) if they are long enough.
+    fn write_zero_lines<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        start: usize,
+        count: usize,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        let len = count * config.bytes_per_line;
+        if len == 0 || len < config.zero_runs.unwrap_or(usize::MAX) {
+            for k in 0..count {
+                let line = if config.reverse { count - 1 - k } else { k };
+                let start = start + line * config.bytes_per_line;
+                self.write_row(f, start..start + config.bytes_per_line)?;
+            }
+            return Ok(());
+        }
+
+        if !config.reverse {
+            self.write_boundary(f, start..start + len)?;
+        }
+        let shade = self.shade(start.div_ceil(config.bytes_per_line), false);
+        f.write_str("\n")?;
+        self.write_prefix(f, start)?;
+        write_shaded(f, shade, |f| {
+            write_separator(f, false, config.group_separator)?;
+            write_zero_run(f, len, config)
+        })?;
+        f.write_str(config.line_suffix)?;
+        if config.reverse {
+            self.write_boundary(f, start..start + len)?;
+        }
+
+        Ok(())
+    }
+
+    /// The end of the run of zero words starting at `start` and ending
+    /// before `end`, if it is at least as long as [`DumpConfig::zero_runs`].
+    ///
+    /// Runs may not contain marked or masked bytes.
+    fn zero_run(&self, start: usize, end: usize) -> Option<usize> {
+        let threshold = self.config.zero_runs?;
+        let word = self.config.word_size;
+        let run = (start..end)
+            .step_by(word)
+            .map(|i| i..min(i + word, end))
+            .take_while(|bytes| {
+                self.bytes[bytes.clone()].iter().all(|&byte| byte == 0)
+                    && self.mark(bytes.clone()).is_none()
+                    && !self.is_padding(bytes.clone())
+                    && !self.is_redacted(bytes.clone())
+            })
+            .last()?
+            .end;
+        (run - start >= threshold).then_some(run)
+    }
+
+    /// Writes the column offsets within a line, aligned with the columns
+    /// after an offset column of `offset_width` characters.
+    fn write_header<W: Write + ?Sized>(&self, f: &mut W, offset_width: usize) -> core::fmt::Result {
+        let config = &self.config;
+        let width = config.digits(config.word_size);
+        write_fill(f, offset_width)?;
+        if config.text_first && config.text_column {
+            f.write_str(" ")?;
+            write_column_digits(f, config)?;
+        }
+        let groups = (0..config.bytes_per_line).step_by(config.group_size);
+        for start in groups.filter(|_| config.hex_column) {
+            write_separator(f, false, config.group_separator)?;
+            let end = min(start + config.group_size, config.bytes_per_line);
+            for column in (start..end).step_by(config.word_size) {
+                if config.radix == Radix::Bits {
+                    for bit in (0..8 * config.word_size).rev() {
+                        f.write_fmt(format_args!(" {}", bit % 8))?;
+                    }
+                    continue;
+                }
+                // the separator and the right alignment of two digits
+                write_fill(f, width - 1)?;
+                if config.uppercase {
+                    f.write_fmt(format_args!("{:02X}", column))?;
+                } else {
+                    f.write_fmt(format_args!("{:02x}", column))?;
+                }
+            }
+        }
+        if !config.text_column || config.text_first {
+            return Ok(());
+        }
+
+        write_separator(f, false, config.column_separator)?;
+        f.write_str(" ")?;
+        write_column_digits(f, config)
+    }
+
+    pub(crate) fn write_line<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        let text_last = config.text_column && !config.text_first;
+        let lead = self.lead(range.len());
+        if config.text_column && config.text_first {
+            f.write_str(" ")?;
+            write_fill(f, lead)?;
+            self.write_text(f, range.clone())?;
+            write_fill(f, config.bytes_per_line - range.len() - lead)?;
+        }
+        let (group_size, word_width) = (config.group_size, 1 + config.digits(config.word_size));
+        for column in (0..lead).step_by(group_size).filter(|_| config.hex_column) {
+            write_separator(f, false, config.group_separator)?;
+            write_fill(
+                f,
+                min(group_size, lead - column).div_ceil(config.word_size) * word_width,
+            )?;
+        }
+        let mut open = None;
+        // the end of the last zero run
+        let mut run_end = range.start;
+        // the column of the byte at `i`
+        let column = |i: usize| i - range.start + lead;
+        let groups = range
+            .clone()
+            .filter(|&i| i == range.start || column(i) % group_size == 0);
+        for start in groups.filter(|_| config.hex_column) {
+            let end = start + group_size - column(start) % group_size;
+            let group = start..min(end, range.end);
+            if group.end <= run_end {
+                continue;
+            }
+            // the first group continues the blank columns of a right-aligned line
+            if start >= run_end && column(start) % group_size == 0 {
+                let first = start..min(start + config.word_size, group.end);
+                let closing = open.is_some() && open != self.mark(first);
+                if closing {
+                    open = None;
+                }
+                write_separator(f, closing, config.group_separator)?;
+            }
+            let shade = self.shade(column(start) / group_size, true);
+            write_shaded(f, shade, |f| {
+                let mut word = max(start, run_end);
+                while word < group.end {
+                    if let Some(end) = self.zero_run(word, range.end).filter(|_| open.is_none()) {
+                        let mut width = Width(0);
+                        write_zero_run(&mut width, end - word, config)?;
+                        write_zero_run(f, end - word, config)?;
+                        let (from, to) = (column(word), column(end));
+                        let mut space = hex_width(to, config) - hex_width(from, config);
+                        if from % group_size == 0 {
+                            space -= 1 + config.group_separator.chars().count();
+                        }
+                        write_fill(f, space.saturating_sub(width.0))?;
+                        run_end = end;
+                        break;
+                    }
+                    let next = min(word + config.word_size, group.end);
+                    self.write_words(f, word..next, &mut open)?;
+                    word = next;
+                }
+
+                Ok(())
+            })?;
+        }
+
+        let checksum = config.checksum.of(&self.bytes[range.clone()]);
+        if !text_last && checksum.is_none() && config.values.is_none() {
+            return if open.is_some() {
+                f.write_str("]")
+            } else {
+                Ok(())
+            };
+        }
+
+        f.write_str(if open.is_some() { "]" } else { " " })?;
+        write_fill(
+            f,
+            hex_width(config.bytes_per_line, config) - hex_width(lead + range.len(), config),
+        )?;
+        f.write_str(config.column_separator)?;
+        f.write_str(" ")?;
+        // the width the previous column is padded to before the next separator
+        let mut fill = None;
+        if text_last {
+            write_fill(f, lead)?;
+            self.write_text(f, range.clone())?;
+            fill = Some(config.bytes_per_line - range.len() - lead);
+        }
+        if let Some(checksum) = checksum {
+            write_column_separator(f, config, fill)?;
+            write_digits(f, checksum.into(), config.digits(1), config)?;
+            fill = Some(0);
+        }
+        if let Some(values) = config.values {
+            write_column_separator(f, config, fill)?;
+            self.write_values(f, values, range)?;
+        }
+
+        Ok(())
+    }
+
+    /// The number of blank columns in front of a line of `len` bytes, to
+    /// [right-align](DumpConfig::right_align_last) a partial last line.
+    fn lead(&self, len: usize) -> usize {
+        let config = &self.config;
+        if !config.right_align_last || len >= config.bytes_per_line {
+            return 0;
+        }
+        (config.bytes_per_line - len) / config.word_size * config.word_size
+    }
+
+    /// Writes the complete values of `values.size()` bytes in `range`, comma separated.
+    fn write_values<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        values: Values,
+        range: Range<usize>,
+    ) -> core::fmt::Result {
+        let size = values.size();
+        for (i, start) in range.clone().step_by(size).enumerate() {
+            if start + size > range.end {
+                break;
+            }
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            if self.is_redacted(start..start + size) {
+                f.write_str("*")?;
+                continue;
+            }
+            let value = self.value(start..start + size);
+            match values {
+                Values::F32 => f.write_fmt(format_args!("{:?}", f32::from_bits(value as u32)))?,
+                Values::F64 => f.write_fmt(format_args!("{:?}", f64::from_bits(value)))?,
+                Values::I8 => f.write_fmt(format_args!("{}", value as i8))?,
+                Values::I16 => f.write_fmt(format_args!("{}", value as i16))?,
+                Values::I32 => f.write_fmt(format_args!("{}", value as i32))?,
+                Values::I64 => f.write_fmt(format_args!("{}", value as i64))?,
+                Values::U8 | Values::U16 | Values::U32 | Values::U64 => {
+                    f.write_fmt(format_args!("{}", value))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the bytes in `range` as a single integer in the configured byte order.
+    fn value(&self, range: Range<usize>) -> u64 {
+        let word = &self.bytes[range];
+        if self.config.endian.is_little() {
+            word.iter()
+                .rev()
+                .fold(0, |value, &byte| value << 8 | byte as u64)
+        } else {
+            word.iter().fold(0, |value, &byte| value << 8 | byte as u64)
+        }
+    }
+
+    /// Writes the bytes in `range` as space separated words of `config.word_size` bytes.
+    ///
+    /// `open` tracks the mark enclosing the previous word, see [`Dump::mark`].
+    fn write_words<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+        open: &mut Option<usize>,
+    ) -> core::fmt::Result {
+        let config = &self.config;
+        let little = config.endian.is_little();
+        for start in range.clone().step_by(config.word_size) {
+            let bytes = start..min(start + config.word_size, range.end);
+            let mut value = self.value(bytes.clone());
+            if config.nibble_swap {
+                value = swap_nibbles(value);
+            }
+            let width = config.digits(bytes.len());
+            // a trailing partial word keeps the width of a full one
+            let missing = config.digits(config.word_size) - width;
+
+            let mark = self.mark(bytes.clone());
+            f.write_str(match (*open, mark) {
+                (open, mark) if open == mark => " ",
+                (None, Some(_)) => "[",
+                (Some(_), None) => "]",
+                // the boundary between two adjacent marks
+                _ => "|",
+            })?;
+            *open = mark;
+            if little {
+                write_fill(f, missing)?;
+            }
+            if self.is_padding(bytes.clone()) {
+                write_repeated(f, self.padding_marker, width)?;
+            } else if self.is_redacted(bytes.clone()) {
+                write_repeated(f, "*", width)?;
+            } else {
+                let reset = self.start_style(f, bytes)?;
+                write_digits(f, value, width, config)?;
+                end_style(f, reset)?;
+            }
+            if !little {
+                write_fill(f, missing)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the character column for `range`, using the rest of the bytes
+    /// to decode characters crossing the boundaries.
+    pub(crate) fn write_text<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+    ) -> core::fmt::Result {
+        let (buf, config) = (self.bytes, &self.config);
+        let mut open = None;
+        for index in range {
+            let char = match config.charset.decode(buf, index) {
+                _ if config.line_endings && buf[index] == b'\n' => '␊',
+                _ if config.line_endings && buf[index] == b'\r' => '␍',
+                Cell::Char(char) => char,
+                Cell::Continuation => config.continuation_placeholder,
+                Cell::None if buf[index] == 0 => {
+                    config.null_placeholder.unwrap_or(config.placeholder)
+                }
+                Cell::None => config.placeholder,
+            };
+
+            let mark = self.mark(index..index + 1);
+            match (open, mark) {
+                (open, mark) if open == mark => {}
+                (None, Some(_)) => f.write_str("[")?,
+                (Some(_), None) => f.write_str("]")?,
+                _ => f.write_str("][")?,
+            }
+            open = mark;
+            if self.is_padding(index..index + 1) {
+                f.write_str(" ")?;
+                continue;
+            }
+            if self.is_redacted(index..index + 1) {
+                f.write_str("*")?;
+                continue;
+            }
+            let reset = self.start_style(f, index..index + 1)?;
+            f.write_char(char)?;
+            end_style(f, reset)?;
+        }
+
+        if open.is_some() {
+            f.write_str("]")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the labels of the annotated regions and the color classes.
+    fn write_legend<W: Write + ?Sized>(&self, f: &mut W) -> core::fmt::Result {
+        #[cfg(feature = "color")]
+        if self.config.color && self.config.color_legend {
+            color::write_legend(f)?;
+        }
+        for region in self.regions {
+            let Range { start, end } = region.range;
+            if self.config.uppercase {
+                f.write_fmt(format_args!(
+                    "\n[{:#04X}..{:#04X}) {}",
+                    start, end, region.label
+                ))?;
+            } else {
+                f.write_fmt(format_args!(
+                    "\n[{:#04x}..{:#04x}) {}",
+                    start, end, region.label
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether any byte in `range` is masked by [`Dump::padding`].
+    fn is_padding(&self, range: Range<usize>) -> bool {
+        range
+            .into_iter()
+            .any(|i| self.padding.get(i).copied().unwrap_or(false))
+    }
+
+    /// Whether any byte in `range` is hidden by [`Dump::redact`].
+    pub(crate) fn is_redacted(&self, range: Range<usize>) -> bool {
+        self.redacted
+            .iter()
+            .any(|redacted| redacted.start < range.end && range.start < redacted.end)
+    }
+
+    fn highlight_at(&self, range: &Range<usize>) -> Option<&Highlight> {
+        self.highlights.iter().find(|h| h.overlaps(range))
+    }
+
+    /// The brackets enclosing the bytes in `range`, identified by the index
+    /// of their highlight followed by the regions.
+    fn mark(&self, range: Range<usize>) -> Option<usize> {
+        let highlight = match self.highlight_at(&range) {
+            #[cfg(feature = "color")]
+            Some(Highlight {
+                style: Style::Ansi(_),
+                ..
+            }) if self.config.color => None,
+            highlight => highlight,
+        };
+        if let Some(highlight) = highlight {
+            return self
+                .highlights
+                .iter()
+                .position(|h| core::ptr::eq(h, highlight));
+        }
+
+        self.regions
+            .iter()
+            .position(|region| region.overlaps(&range))
+            .map(|i| self.highlights.len() + i)
+    }
+
+    /// Starts the colors of the bytes in `range`, returns whether they need a reset.
+    #[cfg(feature = "color")]
+    fn start_style<W: Write + ?Sized>(
+        &self,
+        f: &mut W,
+        range: Range<usize>,
+    ) -> Result<bool, core::fmt::Error> {
+        if !self.config.color || self.is_redacted(range.clone()) {
+            return Ok(false);
+        }
+
+        let mut reset = false;
+        if let Some(class) = Class::of_all(&self.bytes[range.clone()]) {
+            f.write_str(class.style())?;
+            reset = true;
+        }
+        if let Some(Highlight {
+            style: Style::Ansi(sgr),
+            ..
+        }) = self.highlight_at(&range)
+        {
+            f.write_fmt(format_args!("\x1b[{}m", sgr))?;
+            reset = true;
+        }
+        Ok(reset)
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn start_style<W: Write + ?Sized>(
+        &self,
+        _: &mut W,
+        _: Range<usize>,
+    ) -> Result<bool, core::fmt::Error> {
+        Ok(false)
+    }
+
+    /// Whether the line or, with `groups`, the group within its line at
+    /// `index` is [shaded](DumpConfig::shading).
+    #[cfg(feature = "color")]
+    fn shade(&self, index: usize, groups: bool) -> bool {
+        let shading = if groups {
+            Shading::Groups
+        } else {
+            Shading::Lines
+        };
+        self.config.color && self.config.shading == shading && index % 2 == 1
+    }
+
+    #[cfg(not(feature = "color"))]
+    fn shade(&self, _: usize, _: bool) -> bool {
+        false
+    }
+}
+
+impl Debug for Dump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_table(f)
+    }
+}
+
+impl Display for Dump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_table(f)
+    }
+}
+
+/// The most bytes per line [`DumpConfig::fit_width`] considers.
+pub(crate) const MAX_LINE: usize = 256;
+
+/// The width of a full line in characters, without colors and custom prefixes.
+pub(crate) fn line_width(config: &DumpConfig) -> usize {
+    let dump = Dump::new(&[], config).with_addr(0);
+    let mut lead = Width(0);
+    let _ = dump.write_prefix(&mut lead, 0);
+    let mut width = Width(0);
+    let _ = dump.write_layout(&mut width, lead.0);
+    width.0 + config.indent
+}
+
+/// Ends the colors started by [`Dump::start_style`].
+#[cfg(feature = "color")]
+fn end_style<W: Write + ?Sized>(f: &mut W, reset: bool) -> core::fmt::Result {
+    if reset {
+        f.write_str(color::RESET)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "color"))]
+fn end_style<W: Write + ?Sized>(_: &mut W, _: bool) -> core::fmt::Result {
+    Ok(())
+}
+
+/// Writes the output of `write` on a shaded background if `shade` is set.
+#[cfg(feature = "color")]
+fn write_shaded<W: Write + ?Sized>(
+    f: &mut W,
+    shade: bool,
+    write: impl FnOnce(&mut dyn Write) -> core::fmt::Result,
+) -> core::fmt::Result {
+    if !shade {
+        return write(&mut DynWrite(f));
+    }
+    f.write_str(color::SHADE)?;
+    write(&mut Shade(f))?;
+    f.write_str(color::RESET)
+}
+
+#[cfg(not(feature = "color"))]
+fn write_shaded<W: Write + ?Sized>(
+    f: &mut W,
+    _: bool,
+    write: impl FnOnce(&mut dyn Write) -> core::fmt::Result,
+) -> core::fmt::Result {
+    write(&mut DynWrite(f))
+}
+
+/// Writes the separator before a column after the character column,
+/// padding the previous column by `fill` unless it is the first one.
+fn write_column_separator<W: Write + ?Sized>(
+    f: &mut W,
+    config: &DumpConfig,
+    fill: Option<usize>,
+) -> core::fmt::Result {
+    if let Some(width) = fill {
+        write_fill(f, width)?;
+        write_separator(f, false, config.column_separator)?;
+        f.write_str(" ")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `separator` after the closing bracket of a mark or a space.
+fn write_separator<W: Write + ?Sized>(
+    f: &mut W,
+    closing: bool,
+    separator: &str,
+) -> core::fmt::Result {
+    f.write_str(if closing { "]" } else { " " })?;
+    f.write_str(separator)
+}
+
+/// Swaps the high and low nibble of each byte, `0x1A` becomes `0xA1`.
+fn swap_nibbles(value: u64) -> u64 {
+    (value & 0x0F0F_0F0F_0F0F_0F0F) << 4 | (value >> 4) & 0x0F0F_0F0F_0F0F_0F0F
+}
+
+/// Writes the last hex digit of each column offset, the header of the character column.
+fn write_column_digits<W: Write + ?Sized>(f: &mut W, config: &DumpConfig) -> core::fmt::Result {
+    for column in 0..config.bytes_per_line {
+        let digit = char::from_digit(column as u32 % 16, 16).unwrap_or('?');
+        f.write_char(if config.uppercase {
+            digit.to_ascii_uppercase()
+        } else {
+            digit
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Writes the notation of a run of `len` zero bytes.
+fn write_zero_run<W: Write + ?Sized>(
+    f: &mut W,
+    len: usize,
+    config: &DumpConfig,
+) -> core::fmt::Result {
+    f.write_str(" ")?;
+    write_digits(f, 0, config.digits(config.word_size), config)?;
+    f.write_fmt(format_args!(" ×{}", len))
+}
+
+fn write_offset<W: Write + ?Sized>(
+    f: &mut W,
+    config: &DumpConfig,
+    addr: usize,
+    offset: usize,
+) -> core::fmt::Result {
+    let relative = config.base_offset.wrapping_add(offset);
+    let (offset, width) = match config.offsets {
+        Offsets::None => return Ok(()),
+        Offsets::Hex(width) => (relative, width),
+        Offsets::Decimal(width) => return f.write_fmt(format_args!("{:0width$}:", relative)),
+        Offsets::Address => (addr.wrapping_add(offset), 2 * core::mem::size_of::<usize>()),
+    };
+    let (prefix, suffix) = config.offset_notation.affixes();
+    if config.offset_uppercase.unwrap_or(config.uppercase) {
+        f.write_fmt(format_args!("{}{:0width$X}{}:", prefix, offset, suffix))
+    } else {
+        f.write_fmt(format_args!("{}{:0width$x}{}:", prefix, offset, suffix))
+    }
+}
+
+/// Writes `value` in `width` characters, including the [notation](DumpConfig::notation).
+fn write_digits<W: Write + ?Sized>(
+    f: &mut W,
+    value: u64,
+    width: usize,
+    config: &DumpConfig,
+) -> core::fmt::Result {
+    let (prefix, suffix) = config.notation.affixes();
+    let digits = width - prefix.len() - suffix.len();
+    match config.radix {
+        Radix::Hex if config.uppercase => {
+            f.write_fmt(format_args!("{}{:0digits$X}{}", prefix, value, suffix))
+        }
+        Radix::Hex => f.write_fmt(format_args!("{}{:0digits$x}{}", prefix, value, suffix)),
+        Radix::Octal => f.write_fmt(format_args!("{:0width$o}", value)),
+        Radix::Binary => f.write_fmt(format_args!("{:0width$b}", value)),
+        Radix::Bits => {
+            let bits = width.div_ceil(2);
+            for bit in (0..bits).rev() {
+                f.write_str(if value >> bit & 1 != 0 { "1" } else { "0" })?;
+                if bit != 0 {
+                    f.write_str(" ")?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn write_fill<W: Write + ?Sized>(f: &mut W, width: usize) -> core::fmt::Result {
+    write_repeated(f, " ", width)
+}
+
+fn write_repeated<W: Write + ?Sized>(f: &mut W, s: &str, count: usize) -> core::fmt::Result {
+    for _ in 0..count {
+        f.write_str(s)?;
+    }
+
+    Ok(())
+}
+
+/// Counts the characters written to it.
+pub(crate) struct Width(pub(crate) usize);
+
+impl Write for Width {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Passes a writer that may be unsized as `dyn Write`.
+struct DynWrite<'w, W: ?Sized>(&'w mut W);
+
+impl<W: Write + ?Sized> Write for DynWrite<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+/// Restores the shaded background after the colors of a byte are reset.
+#[cfg(feature = "color")]
+struct Shade<'w, W: ?Sized>(&'w mut W);
+
+#[cfg(feature = "color")]
+impl<W: Write + ?Sized> Write for Shade<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for part in s.split_inclusive(color::RESET) {
+            self.0.write_str(part)?;
+            if part.ends_with(color::RESET) {
+                self.0.write_str(color::SHADE)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Drops the line break before the first line and trailing whitespace,
+/// see [`DumpConfig::canonical`].
+struct Canonical<'w, W: ?Sized> {
+    inner: &'w mut W,
+    started: bool,
+    /// Spaces written only once something else follows on the same line.
+    spaces: usize,
+}
+
+impl<W: Write + ?Sized> Write for Canonical<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            let started = core::mem::replace(&mut self.started, true);
+            match char {
+                '\n' if !started => {}
+                '\n' => {
+                    self.spaces = 0;
+                    self.inner.write_char('\n')?;
+                }
+                ' ' => self.spaces += 1,
+                _ => {
+                    write_fill(self.inner, core::mem::take(&mut self.spaces))?;
+                    self.inner.write_char(char)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces everything but printable ASCII and `\n` with `.`.
+struct Plain<'w, W: ?Sized>(&'w mut W);
+
+impl<W: Write + ?Sized> Write for Plain<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            match char {
+                '\n' | ' '..='~' => self.0.write_char(char)?,
+                _ => self.0.write_char('.')?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Closes the lines of a dump from `first` on on the right and draws the
+/// borders above and below them, see [`DumpConfig::box_drawing`].
+struct Boxed<'w, 'a, W: ?Sized> {
+    inner: &'w mut W,
+    dump: &'w Dump<'a>,
+    first: usize,
+    /// The width of everything in front of the bytes on the first line.
+    lead: usize,
+    /// The width of a full line.
+    width: usize,
+    line: usize,
+    /// The visible characters written on the current line.
+    column: usize,
+    /// Whether an ANSI escape code is being written.
+    escape: bool,
+}
+
+impl<W: Write + ?Sized> Boxed<'_, '_, W> {
+    /// Pads the current line to the width of the box and closes it.
+    fn end_line(&mut self) -> core::fmt::Result {
+        if self.line >= self.first {
+            write_fill(self.inner, self.width.saturating_sub(self.column))?;
+            self.inner.write_str(" │")?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the last line and draws the bottom border, if the box was started.
+    fn finish(&mut self) -> core::fmt::Result {
+        if self.line < self.first {
+            return Ok(());
+        }
+        self.end_line()?;
+        self.inner.write_str("\n")?;
+        self.dump
+            .write_border(self.inner, ['└', '┴', '┘'], self.lead)
+    }
+}
+
+impl<W: Write + ?Sized> Write for Boxed<'_, '_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            match char {
+                '\n' => {
+                    self.end_line()?;
+                    self.line += 1;
+                    self.column = 0;
+                    if self.line == self.first {
+                        self.inner.write_char('\n')?;
+                        self.dump
+                            .write_border(self.inner, ['┌', '┬', '┐'], self.lead)?;
+                    }
+                }
+                '\x1B' => self.escape = true,
+                'm' if self.escape => self.escape = false,
+                _ if self.escape => {}
+                _ => self.column += 1,
+            }
+            self.inner.write_char(char)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draws a border of the box along the layout of a line written to it, see
+/// [`Dump::write_border`].
+struct Border<'w, W: ?Sized> {
+    inner: &'w mut W,
+    corners: [char; 3],
+    /// Whether the first column separator was written.
+    started: bool,
+}
+
+impl<W: Write + ?Sized> Write for Border<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            match char {
+                '\n' => {}
+                '│' => {
+                    let corner = if self.started {
+                        self.corners[1]
+                    } else {
+                        self.corners[0]
+                    };
+                    self.inner.write_char(corner)?;
+                    self.started = true;
+                }
+                _ if self.started => self.inner.write_char('─')?,
+                _ => self.inner.write_char(' ')?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes into a byte slice, counting what does not fit.
+struct Slice<'w> {
+    out: &'w mut [u8],
+    len: usize,
+}
+
+impl Write for Slice<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if let Some(out) = self.out.get_mut(self.len..end) {
+            out.copy_from_slice(s.as_bytes());
+        }
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Indents every line after the first by `width` fill characters.
+pub(crate) struct Indent<'w, W: ?Sized> {
+    pub(crate) inner: &'w mut W,
+    pub(crate) fill: char,
+    pub(crate) width: usize,
+}
+
+impl<W: Write + ?Sized> Write for Indent<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for line in s.split_inclusive('\n') {
+            self.inner.write_str(line)?;
+            if line.ends_with('\n') {
+                for _ in 0..self.width {
+                    self.inner.write_char(self.fill)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Width of the hex column for a line of `len` bytes.
+fn hex_width(len: usize, config: &DumpConfig) -> usize {
+    if !config.hex_column {
+        return 0;
+    }
+    let (group, word) = (config.group_size, config.word_size);
+    let word_width = 1 + config.digits(word);
+    let separator = 1 + config.group_separator.chars().count();
+    (0..len)
+        .step_by(group)
+        .map(|start| separator + min(group, len - start).div_ceil(word) * word_width)
+        .sum()
+}
@@ -0,0 +1,201 @@
+//! ELF core files for the `std` feature.
+
+use std::io::{self, Write};
+
+const PT_LOAD: u32 = 1;
+const PT_NOTE: u32 = 4;
+const PF_W: u32 = 2;
+const PF_R: u32 = 4;
+/// The type of the notes with the labels, `MDBG`, distinct from the types
+/// of the notes of Linux cores that gdb reads regardless of their name.
+const NT_LABEL: u32 = 0x4742_444D;
+
+/// A labeled region of memory in an [`ElfCore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoreSegment<'a> {
+    /// The address of the first byte on the target.
+    pub address: u64,
+    /// The bytes of the region.
+    pub bytes: &'a [u8],
+    /// The name of the region, e.g. `"sram"` or `"stack"`.
+    pub label: &'a str,
+}
+
+impl<'a> CoreSegment<'a> {
+    /// The region `bytes` at `address`.
+    pub const fn new(address: u64, bytes: &'a [u8], label: &'a str) -> Self {
+        Self {
+            address,
+            bytes,
+            label,
+        }
+    }
+}
+
+/// A minimal little endian ELF core file of memory regions, e.g. to inspect
+/// the memory of an embedded target with `gdb firmware.elf -c memory.core`.
+///
+/// Each region is a loadable segment at its address. Their labels are
+/// stored in order in a note segment, as notes named `MEMDBG` of the type
+/// `MDBG`.
+///
+/// ```rust
+/// use memdbg::{CoreSegment, ElfCore};
+///
+/// let sram = [0x5A; 64];
+/// let segments = [CoreSegment::new(0x2000_0000, &sram, "sram")];
+/// let mut file = Vec::new();
+/// ElfCore::new(&segments).machine(ElfCore::ARM).elf32(true).write_to(&mut file)?;
+/// assert_eq!(file[..5], *b"\x7FELF\x01");
+/// assert!(file.ends_with(&sram));
+/// let note = b"\x07\0\0\0\x04\0\0\0MDBGMEMDBG\0\0sram";
+/// assert!(file.windows(note.len()).any(|window| window == note));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElfCore<'a> {
+    segments: &'a [CoreSegment<'a>],
+    machine: u16,
+    elf32: bool,
+}
+
+impl<'a> ElfCore<'a> {
+    /// 32 bit ARM, `EM_ARM`.
+    pub const ARM: u16 = 40;
+    /// x86-64, `EM_X86_64`.
+    pub const X86_64: u16 = 62;
+    /// 64 bit ARM, `EM_AARCH64`.
+    pub const AARCH64: u16 = 183;
+    /// RISC-V, `EM_RISCV`.
+    pub const RISCV: u16 = 243;
+
+    /// A 64 bit core file for x86-64 with `segments`.
+    pub fn new(segments: &'a [CoreSegment<'a>]) -> Self {
+        Self {
+            segments,
+            machine: Self::X86_64,
+            elf32: false,
+        }
+    }
+
+    /// Sets the architecture of the target, one of the `EM_` values of ELF.
+    pub fn machine(mut self, machine: u16) -> Self {
+        self.machine = machine;
+        self
+    }
+
+    /// Writes a 32 bit core file, for 32 bit targets.
+    pub fn elf32(mut self, enabled: bool) -> Self {
+        self.elf32 = enabled;
+        self
+    }
+
+    /// Writes the core file to `w`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an address of a 32 bit core file does not fit into 32 bits.
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        let (header_len, program_header_len) = if self.elf32 { (52, 32) } else { (64, 56) };
+        let notes_len: usize = self
+            .segments
+            .iter()
+            .map(|segment| note_len(segment.label))
+            .sum();
+        let headers_len = header_len + program_header_len * (self.segments.len() + 1);
+
+        w.write_all(b"\x7FELF")?;
+        // class, little endian, version, System V ABI and padding
+        w.write_all(&[if self.elf32 { 1 } else { 2 }, 1, 1])?;
+        w.write_all(&[0; 9])?;
+        // a core file of version 1 without entry point
+        w.write_all(&4_u16.to_le_bytes())?;
+        w.write_all(&self.machine.to_le_bytes())?;
+        w.write_all(&1_u32.to_le_bytes())?;
+        self.write_word(&mut w, 0)?;
+        self.write_word(&mut w, header_len as u64)?;
+        // no section headers and flags
+        self.write_word(&mut w, 0)?;
+        w.write_all(&0_u32.to_le_bytes())?;
+        for half in [
+            header_len,
+            program_header_len,
+            self.segments.len() + 1,
+            0,
+            0,
+            0,
+        ] {
+            w.write_all(&(half as u16).to_le_bytes())?;
+        }
+
+        let mut offset = headers_len + notes_len;
+        self.write_program_header(&mut w, PT_NOTE, headers_len, 0, notes_len)?;
+        for segment in self.segments {
+            let len = segment.bytes.len();
+            self.write_program_header(&mut w, PT_LOAD, offset, segment.address, len)?;
+            offset += len;
+        }
+
+        for segment in self.segments {
+            let padding = [0; 3];
+            let label = segment.label.as_bytes();
+            w.write_all(&7_u32.to_le_bytes())?;
+            w.write_all(&(label.len() as u32).to_le_bytes())?;
+            w.write_all(&NT_LABEL.to_le_bytes())?;
+            w.write_all(b"MEMDBG\0\0")?;
+            w.write_all(label)?;
+            w.write_all(&padding[..label.len().next_multiple_of(4) - label.len()])?;
+        }
+        for segment in self.segments {
+            w.write_all(segment.bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes an address, offset or size in the width of the class.
+    fn write_word<W: Write>(&self, w: &mut W, word: u64) -> io::Result<()> {
+        if self.elf32 {
+            let word = u32::try_from(word).expect("address exceeds 32 bits");
+            w.write_all(&word.to_le_bytes())
+        } else {
+            w.write_all(&word.to_le_bytes())
+        }
+    }
+
+    /// Writes the header of a segment, notes are aligned to 4 bytes and
+    /// loadable segments are readable and writable.
+    fn write_program_header<W: Write>(
+        &self,
+        w: &mut W,
+        kind: u32,
+        offset: usize,
+        address: u64,
+        len: usize,
+    ) -> io::Result<()> {
+        let (flags, align) = match kind {
+            PT_NOTE => (0, 4),
+            _ => (PF_R | PF_W, 1),
+        };
+        w.write_all(&kind.to_le_bytes())?;
+        // the flags come first in 64 bit files and last in 32 bit files
+        if !self.elf32 {
+            w.write_all(&flags.to_le_bytes())?;
+        }
+        self.write_word(w, offset as u64)?;
+        self.write_word(w, address)?;
+        self.write_word(w, address)?;
+        self.write_word(w, len as u64)?;
+        self.write_word(w, len as u64)?;
+        if self.elf32 {
+            w.write_all(&flags.to_le_bytes())?;
+        }
+        self.write_word(w, align)
+    }
+}
+
+/// The length of the note holding `label`.
+fn note_len(label: &str) -> usize {
+    // the sizes and type, the padded name and the padded label
+    12 + 8 + label.len().next_multiple_of(4)
+}
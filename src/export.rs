@@ -0,0 +1,761 @@
+//! Encodings of the bytes for other tools, e.g. to flash or embed them.
+
+use core::{
+    cmp::min,
+    fmt::{Debug, Display, Formatter, Write},
+};
+
+/// Intel HEX records of the bytes at an address, for flashing and analysis
+/// tools.
+///
+/// Addresses above 64 KiB use extended linear address records, the last
+/// record is the end of file record. Each record ends with a line break.
+///
+/// ```rust
+/// use memdbg::IntelHex;
+///
+/// let bytes = [
+///     0x21, 0x46, 0x01, 0x36, 0x01, 0x21, 0x47, 0x01, 0x36, 0x00, 0x7E, 0xFE, 0x09, 0xD2, 0x19, 0x01,
+/// ];
+/// assert_eq!(format!("{}", IntelHex::new(&bytes).address(0x0100)), concat!(
+///     ":10010000214601360121470136007EFE09D2190140\n",
+///     ":00000001FF\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct IntelHex<'a> {
+    bytes: &'a [u8],
+    address: u32,
+    record_len: usize,
+}
+
+impl<'a> IntelHex<'a> {
+    /// Encodes `bytes` at address 0 in records of 16 bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            address: 0,
+            record_len: 16,
+        }
+    }
+
+    /// Sets the address of the first byte.
+    pub fn address(mut self, address: u32) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Sets the number of data bytes per record, 16 by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0 or above 255.
+    pub fn record_len(mut self, len: usize) -> Self {
+        assert!((1..=255).contains(&len), "record length must be 1 to 255");
+        self.record_len = len;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // the upper 16 bits of the address, 0 until the first extended address
+        let mut upper = 0;
+        let mut start = 0;
+        while start < self.bytes.len() {
+            let address = self.address.wrapping_add(start as u32);
+            if address >> 16 != upper {
+                upper = address >> 16;
+                write_ihex_record(f, 0x04, 0, &(upper as u16).to_be_bytes())?;
+            }
+            // records may not cross a 64 KiB boundary
+            let room = 0x1_0000 - (address & 0xFFFF) as usize;
+            let len = min(min(self.record_len, room), self.bytes.len() - start);
+            write_ihex_record(f, 0x00, address as u16, &self.bytes[start..start + len])?;
+            start += len;
+        }
+
+        write_ihex_record(f, 0x01, 0, &[])
+    }
+}
+
+impl Debug for IntelHex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        IntelHex::fmt(self, f)
+    }
+}
+
+impl Display for IntelHex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        IntelHex::fmt(self, f)
+    }
+}
+
+/// Writes an Intel HEX record of `kind` with its checksum.
+fn write_ihex_record<W: Write + ?Sized>(
+    f: &mut W,
+    kind: u8,
+    address: u16,
+    data: &[u8],
+) -> core::fmt::Result {
+    let [high, low] = address.to_be_bytes();
+    let header = [data.len() as u8, high, low, kind];
+    f.write_str(":")?;
+    write_hex(f, &header)?;
+    write_hex(f, data)?;
+    let sum = header
+        .iter()
+        .chain(data)
+        .fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    write_hex(f, &[sum.wrapping_neg()])?;
+    f.write_str("\n")
+}
+
+/// The address width of [`Srec`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SrecFormat {
+    /// 16 bit addresses, `S1` records terminated by `S9`.
+    S19,
+    /// 24 bit addresses, `S2` records terminated by `S8`.
+    S28,
+    /// 32 bit addresses, `S3` records terminated by `S7`.
+    S37,
+}
+
+impl SrecFormat {
+    /// The smallest format that can address `end`, the address after the last byte.
+    fn fitting(end: u64) -> Self {
+        match end {
+            0..=0x1_0000 => SrecFormat::S19,
+            0x1_0001..=0x100_0000 => SrecFormat::S28,
+            _ => SrecFormat::S37,
+        }
+    }
+
+    const fn address_len(self) -> usize {
+        match self {
+            SrecFormat::S19 => 2,
+            SrecFormat::S28 => 3,
+            SrecFormat::S37 => 4,
+        }
+    }
+
+    /// The digits of the data and the termination record types.
+    const fn records(self) -> (char, char) {
+        match self {
+            SrecFormat::S19 => ('1', '9'),
+            SrecFormat::S28 => ('2', '8'),
+            SrecFormat::S37 => ('3', '7'),
+        }
+    }
+}
+
+/// Motorola S-records of the bytes at an address, for device programmers.
+///
+/// The records start with an `S0` header and end with the termination
+/// record of the [format](Srec::format). Each record ends with a line break.
+///
+/// ```rust
+/// use memdbg::Srec;
+///
+/// assert_eq!(format!("{}", Srec::new(b"memdbg").address(0x1000)), concat!(
+///     "S0030000FC\n",
+///     "S10910006D656D6462677A\n",
+///     "S9030000FC\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Srec<'a> {
+    bytes: &'a [u8],
+    address: u32,
+    format: Option<SrecFormat>,
+    header: &'a str,
+    record_len: usize,
+}
+
+impl<'a> Srec<'a> {
+    /// Encodes `bytes` at address 0 in records of 16 bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            address: 0,
+            format: None,
+            header: "",
+            record_len: 16,
+        }
+    }
+
+    /// Sets the address of the first byte.
+    pub fn address(mut self, address: u32) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Sets the address width, by default the smallest one that fits the
+    /// addresses. Larger addresses are truncated.
+    pub fn format(mut self, format: SrecFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the text of the `S0` header record, e.g. a file or module name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `header` is longer than 252 bytes.
+    pub fn header(mut self, header: &'a str) -> Self {
+        assert!(header.len() <= 252, "header must be at most 252 bytes");
+        self.header = header;
+        self
+    }
+
+    /// Sets the number of data bytes per record, 16 by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0 or above 250.
+    pub fn record_len(mut self, len: usize) -> Self {
+        assert!((1..=250).contains(&len), "record length must be 1 to 250");
+        self.record_len = len;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let end = u64::from(self.address) + self.bytes.len() as u64;
+        let format = self.format.unwrap_or(SrecFormat::fitting(end));
+        let (data, termination) = format.records();
+        write_srec(f, '0', 0, 2, self.header.as_bytes())?;
+        for (i, record) in self.bytes.chunks(self.record_len).enumerate() {
+            let address = self.address.wrapping_add((i * self.record_len) as u32);
+            write_srec(f, data, address, format.address_len(), record)?;
+        }
+
+        write_srec(f, termination, 0, format.address_len(), &[])
+    }
+}
+
+impl Debug for Srec<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Srec::fmt(self, f)
+    }
+}
+
+impl Display for Srec<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Srec::fmt(self, f)
+    }
+}
+
+/// Writes an S-record of `kind` with the lowest `address_len` bytes of
+/// `address` and its checksum.
+fn write_srec<W: Write + ?Sized>(
+    f: &mut W,
+    kind: char,
+    address: u32,
+    address_len: usize,
+    data: &[u8],
+) -> core::fmt::Result {
+    let address = &address.to_be_bytes()[4 - address_len..];
+    let count = (address.len() + data.len() + 1) as u8;
+    f.write_fmt(format_args!("S{}", kind))?;
+    write_hex(f, &[count])?;
+    write_hex(f, address)?;
+    write_hex(f, data)?;
+    let sum = address
+        .iter()
+        .chain(data)
+        .fold(count, |sum, &byte| sum.wrapping_add(byte));
+    write_hex(f, &[!sum])?;
+    f.write_str("\n")
+}
+
+/// The bytes in Base64 with padding, e.g. to embed them in JSON logs or
+/// bug reports.
+///
+/// ```rust
+/// use memdbg::Base64;
+///
+/// assert_eq!(format!("{}", Base64::new(b"memdbg!")), "bWVtZGJnIQ==");
+/// assert_eq!(format!("{}", Base64::new(b"memdbg!").wrap(4)), "bWVt\nZGJn\nIQ==");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Base64<'a> {
+    bytes: &'a [u8],
+    wrap: Option<usize>,
+    url_safe: bool,
+}
+
+impl<'a> Base64<'a> {
+    /// Encodes `bytes` on a single line with the standard alphabet.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            wrap: None,
+            url_safe: false,
+        }
+    }
+
+    /// Breaks the lines after `columns` characters, e.g. 76 for MIME.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is 0.
+    pub fn wrap(mut self, columns: usize) -> Self {
+        assert!(columns > 0, "columns must not be 0");
+        self.wrap = Some(columns);
+        self
+    }
+
+    /// Uses the URL and filename safe alphabet with `-` and `_`.
+    pub fn url_safe(mut self, enabled: bool) -> Self {
+        self.url_safe = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let encode = |sextet: u32| match ALPHABET[sextet as usize & 0x3F] {
+            b'+' if self.url_safe => '-',
+            b'/' if self.url_safe => '_',
+            char => char as char,
+        };
+
+        let mut column = 0;
+        for chunk in self.bytes.chunks(3) {
+            let mut bytes = [0; 3];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+            for i in 0..4 {
+                if self.wrap == Some(column) {
+                    f.write_str("\n")?;
+                    column = 0;
+                }
+                column += 1;
+                if i > chunk.len() {
+                    f.write_str("=")?;
+                    continue;
+                }
+                f.write_char(encode(bits >> (18 - 6 * i)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Base64<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Base64::fmt(self, f)
+    }
+}
+
+impl Display for Base64<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Base64::fmt(self, f)
+    }
+}
+
+/// The bytes uuencoded for mail based workflows, with `` ` `` for zero
+/// like GNU `uuencode` and a line break after each line.
+///
+/// ```rust
+/// use memdbg::Uuencode;
+///
+/// assert_eq!(format!("{}", Uuencode::new(b"memdbg!").name("dump.bin")), concat!(
+///     "begin 644 dump.bin\n",
+///     "';65M9&)G(0``\n",
+///     "`\n",
+///     "end\n",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Uuencode<'a> {
+    bytes: &'a [u8],
+    name: &'a str,
+    mode: u32,
+}
+
+impl<'a> Uuencode<'a> {
+    /// Encodes `bytes` as the file `buf` with mode 644.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            name: "buf",
+            mode: 0o644,
+        }
+    }
+
+    /// Sets the name of the decoded file.
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the permissions of the decoded file, e.g. `0o600`.
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let encode = |sextet: u32| match sextet & 0x3F {
+            0 => '`',
+            sextet => char::from(sextet as u8 + b' '),
+        };
+
+        f.write_fmt(format_args!("begin {:03o} {}\n", self.mode, self.name))?;
+        for line in self.bytes.chunks(45) {
+            f.write_char(encode(line.len() as u32))?;
+            for chunk in line.chunks(3) {
+                let mut bytes = [0; 3];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+                (0..4).try_for_each(|i| f.write_char(encode(bits >> (18 - 6 * i))))?;
+            }
+            f.write_str("\n")?;
+        }
+
+        f.write_str("`\nend\n")
+    }
+}
+
+impl Debug for Uuencode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Uuencode::fmt(self, f)
+    }
+}
+
+impl Display for Uuencode<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Uuencode::fmt(self, f)
+    }
+}
+
+/// The bytes in quoted-printable, printable ASCII stays readable and
+/// everything else becomes `=XX`.
+///
+/// Line breaks in the bytes are encoded as well, the lines of the output
+/// end with soft line breaks, `=`, to stay within 76 characters.
+///
+/// ```rust
+/// use memdbg::QuotedPrintable;
+///
+/// let qp = QuotedPrintable::new(b"size = 4\r\n\xDE\xAD ");
+/// assert_eq!(format!("{qp}"), "size =3D 4=0D=0A=DE=AD=20");
+/// ```
+#[derive(Clone, Copy)]
+pub struct QuotedPrintable<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> QuotedPrintable<'a> {
+    /// Encodes `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        const MAX_LINE: usize = 76;
+        let mut column = 0;
+        for (i, &byte) in self.bytes.iter().enumerate() {
+            // whitespace at the end of a line would be dropped by mail servers
+            let last = i + 1 == self.bytes.len();
+            let literal = match byte {
+                b' ' | b'\t' => !last,
+                b'=' => false,
+                byte => byte.is_ascii_graphic(),
+            };
+            let len = if literal { 1 } else { 3 };
+            // room for the `=` of a soft line break, unless this is the end
+            if column + len > MAX_LINE - usize::from(!last) {
+                f.write_str("=\n")?;
+                column = 0;
+            }
+            column += len;
+            match literal {
+                true => f.write_char(byte as char)?,
+                false => f.write_fmt(format_args!("={:02X}", byte))?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for QuotedPrintable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        QuotedPrintable::fmt(self, f)
+    }
+}
+
+impl Display for QuotedPrintable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        QuotedPrintable::fmt(self, f)
+    }
+}
+
+/// The bytes as a C array initializer, e.g. for firmware test fixtures.
+///
+/// C has no empty arrays, the array of no bytes holds a single zero and
+/// says so in a comment.
+///
+/// ```rust
+/// use memdbg::CArray;
+///
+/// let array = CArray::new(b"memdbg\0").name("expected").per_line(4);
+/// assert_eq!(format!("{array}"), concat!(
+///     "static const uint8_t expected[] = {\n",
+///     "    0x6d, 0x65, 0x6d, 0x64,\n",
+///     "    0x62, 0x67, 0x00\n",
+///     "};",
+/// ));
+/// assert_eq!(
+///     format!("{}", CArray::new(&[])),
+///     "static const uint8_t buf[] = { 0 }; /* 0 bytes */",
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct CArray<'a> {
+    bytes: &'a [u8],
+    name: &'a str,
+    per_line: usize,
+}
+
+impl<'a> CArray<'a> {
+    /// Declares `bytes` as `buf` with 12 bytes per line, like `xxd -i`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            name: "buf",
+            per_line: 12,
+        }
+    }
+
+    /// Sets the name of the array.
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the number of bytes per line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is 0.
+    pub fn per_line(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "bytes per line must not be 0");
+        self.per_line = bytes;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("static const uint8_t {}[] = {{", self.name))?;
+        if self.bytes.is_empty() {
+            return f.write_str(" 0 }; /* 0 bytes */");
+        }
+        write_array_lines(f, self.bytes, self.per_line, "0x", "")?;
+        f.write_str("\n};")
+    }
+}
+
+impl Debug for CArray<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        CArray::fmt(self, f)
+    }
+}
+
+impl Display for CArray<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        CArray::fmt(self, f)
+    }
+}
+
+/// The bytes as a Rust literal, e.g. to paste a capture into a unit test
+/// as the expected value.
+///
+/// Mostly printable bytes are written as a byte string, `*b"..."` is a
+/// `[u8; N]` as well, others as an array.
+///
+/// ```rust
+/// use memdbg::RustLiteral;
+///
+/// assert_eq!(format!("{}", RustLiteral::new(b"GET /\r\n")), r#"b"GET /\r\n""#);
+/// assert_eq!(
+///     format!("{}", RustLiteral::new(&[0xDE, 0xAD, 0xBE, 0xEF])),
+///     "[\n    0xde, 0xad, 0xbe, 0xef,\n]",
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct RustLiteral<'a> {
+    bytes: &'a [u8],
+    byte_string: Option<bool>,
+    per_line: usize,
+}
+
+impl<'a> RustLiteral<'a> {
+    /// Writes `bytes` as a byte string if at least three quarters of them
+    /// are printable, otherwise as an array of 12 bytes per line.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            byte_string: None,
+            per_line: 12,
+        }
+    }
+
+    /// Always writes a byte string or always an array.
+    pub fn byte_string(mut self, enabled: bool) -> Self {
+        self.byte_string = Some(enabled);
+        self
+    }
+
+    /// Sets the number of bytes per line of an array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is 0.
+    pub fn per_line(mut self, bytes: usize) -> Self {
+        assert!(bytes > 0, "bytes per line must not be 0");
+        self.per_line = bytes;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let printable = self
+            .bytes
+            .iter()
+            .filter(|&&byte| byte.is_ascii_graphic() || b" \t\r\n".contains(&byte))
+            .count();
+        if !self
+            .byte_string
+            .unwrap_or(4 * printable >= 3 * self.bytes.len())
+        {
+            f.write_str("[")?;
+            write_array_lines(f, self.bytes, self.per_line, "0x", "")?;
+            if !self.bytes.is_empty() {
+                f.write_str(",\n")?;
+            }
+            return f.write_str("]");
+        }
+
+        f.write_str("b\"")?;
+        for &byte in self.bytes {
+            match byte {
+                b'"' => f.write_str("\\\"")?,
+                b'\\' => f.write_str("\\\\")?,
+                b'\t' => f.write_str("\\t")?,
+                b'\r' => f.write_str("\\r")?,
+                b'\n' => f.write_str("\\n")?,
+                b'\0' => f.write_str("\\0")?,
+                b' '..=b'~' => f.write_char(byte as char)?,
+                _ => f.write_fmt(format_args!("\\x{:02x}", byte))?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+impl Debug for RustLiteral<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        RustLiteral::fmt(self, f)
+    }
+}
+
+impl Display for RustLiteral<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        RustLiteral::fmt(self, f)
+    }
+}
+
+/// A pcap file of packets, e.g. to dissect captured network buffers in
+/// Wireshark or tcpdump.
+///
+/// ```rust
+/// use core::time::Duration;
+/// use memdbg::Pcap;
+///
+/// let packets = [(Duration::from_millis(1_700_000_000_250), &b"\x45\x00\x00\x14"[..])];
+/// let mut file = Vec::new();
+/// Pcap::new(Pcap::RAW).write_to(&mut file, packets)?;
+/// assert_eq!(file.len(), 24 + 16 + 4);
+/// assert_eq!(file[..4], [0xD4, 0xC3, 0xB2, 0xA1]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pcap {
+    link_type: u32,
+}
+
+#[cfg(feature = "std")]
+impl Pcap {
+    /// Ethernet frames.
+    pub const ETHERNET: u32 = 1;
+    /// Raw IPv4 or IPv6 packets.
+    pub const RAW: u32 = 101;
+
+    /// Packets of the link layer `link_type`, one of the `LINKTYPE_` values
+    /// of libpcap.
+    pub fn new(link_type: u32) -> Self {
+        Self { link_type }
+    }
+
+    /// Writes the file header and a record per packet, with the time since
+    /// the Unix epoch it was captured at. Packets are written in full, up
+    /// to 256 KiB.
+    pub fn write_to<'p, W: std::io::Write>(
+        &self,
+        mut w: W,
+        packets: impl IntoIterator<Item = (core::time::Duration, &'p [u8])>,
+    ) -> std::io::Result<()> {
+        const SNAP_LEN: u32 = 0x4_0000;
+        // magic, version 2.4, time zone, accuracy, snapshot length, link type
+        w.write_all(&0xA1B2_C3D4_u32.to_le_bytes())?;
+        w.write_all(&[2, 0, 4, 0])?;
+        w.write_all(&[0; 8])?;
+        w.write_all(&SNAP_LEN.to_le_bytes())?;
+        w.write_all(&self.link_type.to_le_bytes())?;
+        for (time, packet) in packets {
+            let len = u32::try_from(packet.len()).unwrap_or(u32::MAX);
+            let captured = min(len, SNAP_LEN);
+            w.write_all(&(time.as_secs() as u32).to_le_bytes())?;
+            w.write_all(&time.subsec_micros().to_le_bytes())?;
+            w.write_all(&captured.to_le_bytes())?;
+            w.write_all(&len.to_le_bytes())?;
+            w.write_all(&packet[..captured as usize])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the bytes comma separated on indented lines of `per_line` bytes,
+/// each as `prefix`, two hex digits and `suffix`.
+fn write_array_lines<W: Write + ?Sized>(
+    f: &mut W,
+    bytes: &[u8],
+    per_line: usize,
+    prefix: &str,
+    suffix: &str,
+) -> core::fmt::Result {
+    for (i, line) in bytes.chunks(per_line).enumerate() {
+        if i != 0 {
+            f.write_str(",")?;
+        }
+        f.write_str("\n    ")?;
+        for (j, byte) in line.iter().enumerate() {
+            if j != 0 {
+                f.write_str(", ")?;
+            }
+            f.write_fmt(format_args!("{}{:02x}{}", prefix, byte, suffix))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the bytes as uppercase hex digits without separators.
+fn write_hex<W: Write + ?Sized>(f: &mut W, bytes: &[u8]) -> core::fmt::Result {
+    bytes
+        .iter()
+        .try_for_each(|byte| f.write_fmt(format_args!("{:02X}", byte)))
+}
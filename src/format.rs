@@ -0,0 +1,195 @@
+//! Custom output formats, fed the rows of a dump.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::ops::Range;
+
+use crate::Dump;
+
+/// An output format written row by row, see [`Dump::format`].
+///
+/// The dump splits the bytes into rows of
+/// [`bytes_per_line`](crate::DumpConfig::bytes_per_line) bytes, starts a
+/// row at each aligned address with
+/// [`address_alignment`](crate::DumpConfig::address_alignment) and
+/// collapses repeated rows with [`squeeze`](crate::DumpConfig::squeeze) and
+/// [`squeeze_context`](crate::DumpConfig::squeeze_context), the format
+/// only writes them. The built-in [`Csv`](crate::Csv), JSON and Markdown
+/// table output are formats as well.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use memdbg::{Buf, DumpConfig, DumpFormat, Row};
+///
+/// /// Writes each row as `offset=hex`.
+/// struct Pairs;
+///
+/// impl DumpFormat for Pairs {
+///     fn row(&self, f: &mut dyn Write, row: &Row<'_>) -> core::fmt::Result {
+///         write!(f, "{:#x}=", row.offset())?;
+///         (0..row.bytes().len()).try_for_each(|i| row.write_byte(f, i))?;
+///         f.write_str("\n")
+///     }
+/// }
+///
+/// let buf = Buf([0x11, 0x22, 0, 0, 0, 0, 0, 0, 0x33]);
+/// let config = DumpConfig::portable().bytes_per_line(2).base_offset(0x10).squeeze(true);
+/// assert_eq!(format!("{}", buf.display_with(&config).format(Pairs)), concat!(
+///     "0x10=1122\n",
+///     "0x12=0000\n",
+///     "*\n",
+///     "0x18=33\n",
+/// ));
+/// ```
+pub trait DumpFormat {
+    /// Writes everything in front of the rows of `bytes_per_line` bytes,
+    /// e.g. a header. Writes nothing by default.
+    fn begin(&self, f: &mut dyn Write, bytes_per_line: usize) -> core::fmt::Result {
+        let _ = (f, bytes_per_line);
+        Ok(())
+    }
+
+    /// Writes a row of the dump.
+    fn row(&self, f: &mut dyn Write, row: &Row<'_>) -> core::fmt::Result;
+
+    /// Writes the `count` rows collapsed since they repeat the row in front
+    /// of them, starting with `first`. Writes `*` on its own line by
+    /// default, like `xxd`.
+    fn squeezed(&self, f: &mut dyn Write, first: &Row<'_>, count: usize) -> core::fmt::Result {
+        let _ = (first, count);
+        f.write_str("*\n")
+    }
+
+    /// Writes everything after the rows. Writes nothing by default.
+    fn end(&self, f: &mut dyn Write) -> core::fmt::Result {
+        let _ = f;
+        Ok(())
+    }
+}
+
+/// A row of a dump, passed to [`DumpFormat::row`].
+#[derive(Clone, Copy)]
+pub struct Row<'a> {
+    dump: &'a Dump<'a>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Row<'a> {
+    /// The offset of the first byte, including the
+    /// [`base_offset`](crate::DumpConfig::base_offset).
+    pub fn offset(&self) -> usize {
+        self.dump.config().base_offset.wrapping_add(self.start)
+    }
+
+    /// The indices of the bytes of the row in the dump.
+    #[cfg(feature = "json")]
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// The bytes of the row, fewer than a full row at the start and end of
+    /// the dump.
+    pub fn bytes(&self) -> &'a [u8] {
+        &self.dump.bytes()[self.start..self.end]
+    }
+
+    /// Writes the byte at `index` in the row like the hex column, in the
+    /// [radix](crate::DumpConfig::radix) of the config, `--` for padding
+    /// and `**` if redacted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not within the row.
+    pub fn write_byte(&self, f: &mut dyn Write, index: usize) -> core::fmt::Result {
+        let bytes = self.bytes();
+        self.dump.write_byte(f, self.start + index, bytes[index])
+    }
+
+    /// Writes the characters of the row like the text column, decoded with
+    /// the [charset](crate::DumpConfig::charset) of the config.
+    pub fn write_text(&self, f: &mut dyn Write) -> core::fmt::Result {
+        self.dump.write_text(f, self.start..self.end)
+    }
+}
+
+/// A dump written by a [`DumpFormat`], created by [`Dump::format`].
+#[derive(Clone, Copy)]
+pub struct Formatted<'a, F> {
+    dump: Dump<'a>,
+    format: F,
+}
+
+impl<'a, F: DumpFormat> Formatted<'a, F> {
+    pub(crate) fn new(dump: Dump<'a>, format: F) -> Self {
+        Self { dump, format }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (bytes, config) = (self.dump.bytes(), self.dump.config());
+        self.format.begin(f, config.bytes_per_line)?;
+        let mut previous = None;
+        // the squeezed rows, from the first to the last of them
+        let mut squeezed = None;
+        for range in self.dump.rows() {
+            if self.dump.repeats(range.clone(), previous) {
+                let first = squeezed.map_or(range.start, |(first, _)| first);
+                squeezed = Some((first, range));
+                continue;
+            }
+            if let Some(rows) = squeezed.take() {
+                self.write_squeezed(f, rows)?;
+            }
+            previous = Some(&bytes[range.clone()]);
+            self.format.row(f, &self.row(range))?;
+        }
+        if let Some(rows) = squeezed {
+            self.write_squeezed(f, rows)?;
+        }
+
+        self.format.end(f)
+    }
+
+    fn row(&self, range: Range<usize>) -> Row<'_> {
+        Row {
+            dump: &self.dump,
+            start: range.start,
+            end: range.end,
+        }
+    }
+
+    /// Writes the squeezed rows from the one at `first` to `last`, keeping
+    /// `last` with [`squeeze_context`](crate::DumpConfig::squeeze_context).
+    fn write_squeezed(
+        &self,
+        f: &mut dyn Write,
+        (first, last): (usize, Range<usize>),
+    ) -> core::fmt::Result {
+        let line_len = self.dump.config().bytes_per_line;
+        let mut count = (last.start - first) / line_len + 1;
+        let context = self.dump.config().squeeze_context;
+        if context {
+            count -= 1;
+        }
+        if count != 0 {
+            self.format
+                .squeezed(f, &self.row(first..first + line_len), count)?;
+        }
+        if context {
+            self.format.row(f, &self.row(last))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: DumpFormat> Debug for Formatted<'_, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Formatted::fmt(self, f)
+    }
+}
+
+impl<F: DumpFormat> Display for Formatted<'_, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Formatted::fmt(self, f)
+    }
+}
@@ -0,0 +1,67 @@
+use core::ops::Range;
+
+/// Marks a range of bytes in both columns of a dump, see [`Dump::highlight`](crate::Dump::highlight).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Highlight {
+    /// The highlighted bytes, relative to the start of the dump.
+    pub range: Range<usize>,
+    /// How the bytes are marked.
+    pub style: Style,
+}
+
+impl Highlight {
+    /// Highlights the bytes in `range` with `style`.
+    pub const fn new(range: Range<usize>, style: Style) -> Self {
+        Self { range, style }
+    }
+
+    pub(crate) fn overlaps(&self, range: &Range<usize>) -> bool {
+        self.range.start < range.end && range.start < self.range.end
+    }
+}
+
+/// How a [`Highlight`] marks its bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Encloses the bytes in `[` and `]`.
+    Brackets,
+    /// Displays the bytes with the given ANSI SGR parameters, e.g. `"7"` for
+    /// inverse or `"41"` for a red background.
+    ///
+    /// Falls back to [`Style::Brackets`] if colors are disabled or the
+    /// `color` feature is off.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, Highlight, Style};
+    ///
+    /// let highlights = [Highlight::new(1..2, Style::Ansi("7"))];
+    /// let buf = Buf(*b"ABC");
+    /// let config = DumpConfig::portable().bytes_per_line(3);
+    /// let dump = buf.display_with(&config).highlight(&highlights);
+    /// assert_eq!(format!("{dump:?}"), "\n | 41[42]43 | A[B]C");
+    /// ```
+    Ansi(&'static str),
+}
+
+/// A named region of a dump, see [`Dump::annotate`](crate::Dump::annotate).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Region<'a> {
+    /// The bytes of the region, relative to the start of the dump.
+    pub range: Range<usize>,
+    /// The name of the region, e.g. the field it contains.
+    pub label: &'a str,
+}
+
+impl<'a> Region<'a> {
+    /// A region of `len` bytes starting at `offset`.
+    pub const fn new(offset: usize, len: usize, label: &'a str) -> Self {
+        Self {
+            range: offset..offset + len,
+            label,
+        }
+    }
+
+    pub(crate) fn overlaps(&self, range: &Range<usize>) -> bool {
+        self.range.start < range.end && range.start < self.range.end
+    }
+}
@@ -0,0 +1,128 @@
+//! HTML output for the `html` feature.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{color, Dump};
+
+/// A hex dump as a `<pre>` element for web based log viewers and reports.
+///
+/// Created by [`Dump::html`]. Bytes are wrapped in `<span>`s with the class
+/// of their color, `zero`, `printable`, `control` or `high-bit`, shaded
+/// lines and groups in `shade` and [`Style::Ansi`](crate::Style::Ansi)
+/// highlights in `sgr-` followed by their parameters, e.g. `sgr-41`.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig, Highlight, Style};
+///
+/// let buf = Buf(*b"<a>\0");
+/// let highlights = [Highlight::new(1..2, Style::Ansi("41"))];
+/// let dump = buf.display_with(&DumpConfig::portable()).highlight(&highlights);
+/// let html = format!("{}", dump.html());
+/// assert!(html.starts_with(r#"<pre class="memdbg">"#));
+/// assert!(html.contains(r#"<span class="printable"><span class="sgr-41">61</span></span>"#));
+/// assert!(html.contains(r#"<span class="printable">&lt;</span>"#));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Html<'a> {
+    dump: Dump<'a>,
+}
+
+impl<'a> Html<'a> {
+    pub(crate) fn new(dump: Dump<'a>) -> Self {
+        Self { dump }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(r#"<pre class="memdbg">"#)?;
+        let mut spans = Spans {
+            inner: f,
+            sgr: None,
+            open: 0,
+        };
+        self.dump.write(&mut spans)?;
+        spans.close()?;
+        f.write_str("</pre>")
+    }
+}
+
+impl Debug for Html<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Html::fmt(self, f)
+    }
+}
+
+impl Display for Html<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Html::fmt(self, f)
+    }
+}
+
+/// Escapes the text for HTML and replaces ANSI colors with `<span>`s.
+struct Spans<'w, W: ?Sized> {
+    inner: &'w mut W,
+    /// The parameters of the escape sequence being written.
+    sgr: Option<([u8; 16], usize)>,
+    /// The number of open `<span>`s.
+    open: usize,
+}
+
+impl<W: Write + ?Sized> Spans<'_, W> {
+    fn close(&mut self) -> core::fmt::Result {
+        for _ in 0..self.open {
+            self.inner.write_str("</span>")?;
+        }
+        self.open = 0;
+        Ok(())
+    }
+
+    fn open(&mut self, sgr: &str) -> core::fmt::Result {
+        self.inner.write_str(r#"<span class=""#)?;
+        match color::class_name(sgr) {
+            Some(name) => self.inner.write_str(name)?,
+            None => {
+                self.inner.write_str("sgr")?;
+                for parameter in sgr.split(';') {
+                    self.inner.write_fmt(format_args!("-{}", parameter))?;
+                }
+            }
+        }
+        self.open += 1;
+        self.inner.write_str(r#"">"#)
+    }
+}
+
+impl<W: Write + ?Sized> Write for Spans<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            if let Some((mut sgr, len)) = self.sgr {
+                self.sgr = match char {
+                    'm' => {
+                        let sgr = core::str::from_utf8(&sgr[..len]).unwrap_or_default();
+                        match sgr {
+                            "0" => self.close()?,
+                            sgr => self.open(sgr)?,
+                        }
+                        None
+                    }
+                    '[' => Some((sgr, len)),
+                    _ if len < sgr.len() => {
+                        sgr[len] = char as u8;
+                        Some((sgr, len + 1))
+                    }
+                    _ => Some((sgr, len)),
+                };
+                continue;
+            }
+            match char {
+                '\x1b' => self.sgr = Some(([0; 16], 0)),
+                '<' => self.inner.write_str("&lt;")?,
+                '>' => self.inner.write_str("&gt;")?,
+                '&' => self.inner.write_str("&amp;")?,
+                '"' => self.inner.write_str("&quot;")?,
+                char => self.inner.write_char(char)?,
+            }
+        }
+
+        Ok(())
+    }
+}
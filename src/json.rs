@@ -0,0 +1,213 @@
+//! JSON output for the `json` feature.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{Dump, DumpFormat, Formatted, Region, Row};
+
+/// A hex dump as a JSON object for log pipelines to index and query.
+///
+/// Created by [`Dump::json`]. Each line is an object with the `offset` of
+/// its first byte, its bytes as `hex` digits, its characters as `text` and
+/// the regions of [`Dump::annotate`] it overlaps as `annotations`. Offsets
+/// include the [`base_offset`](crate::DumpConfig::base_offset).
+///
+/// The lines are split like those of a [`DumpFormat`], lines collapsed by
+/// [`squeeze`](crate::DumpConfig::squeeze) are an object with the `offset`
+/// of the first of them and the number of `repeats`.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig};
+///
+/// let config = DumpConfig::portable().bytes_per_line(2).squeeze(true);
+/// assert_eq!(format!("{}", Buf([0; 6]).display_with(&config).json()), concat!(
+///     r#"{"lines":[{"offset":0,"hex":"0000","text":"..","annotations":[]},"#,
+///     r#"{"offset":2,"repeats":2}]}"#,
+/// ));
+/// ```
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig, Region};
+///
+/// let buf = Buf(*b"\x7FELF\x02\x01\"\\");
+/// let regions = [Region::new(0, 4, "magic")];
+/// let config = DumpConfig::portable().bytes_per_line(4).uppercase(false);
+/// let dump = buf.display_with(&config).annotate(&regions);
+/// assert_eq!(format!("{}", dump.json()), concat!(
+///     r#"{"lines":["#,
+///     r#"{"offset":0,"hex":"7f454c46","text":".ELF","annotations":[{"start":0,"end":4,"label":"magic"}]},"#,
+///     r#"{"offset":4,"hex":"0201225c","text":"..\"\\","annotations":[]}"#,
+///     r#"]}"#,
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Json<'a> {
+    dump: Dump<'a>,
+    regions: &'a [Region<'a>],
+}
+
+impl<'a> Json<'a> {
+    pub(crate) fn new(dump: Dump<'a>, regions: &'a [Region<'a>]) -> Self {
+        Self { dump, regions }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let lines = Lines {
+            regions: self.regions,
+            base_offset: self.dump.config().base_offset,
+        };
+        Display::fmt(&Formatted::new(self.dump, lines), f)
+    }
+}
+
+impl Debug for Json<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Json::fmt(self, f)
+    }
+}
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Json::fmt(self, f)
+    }
+}
+
+/// Writes the lines of a [`Json`].
+struct Lines<'a> {
+    regions: &'a [Region<'a>],
+    base_offset: usize,
+}
+
+impl DumpFormat for Lines<'_> {
+    fn begin(&self, f: &mut dyn Write, _: usize) -> core::fmt::Result {
+        f.write_str(r#"{"lines":["#)
+    }
+
+    fn row(&self, f: &mut dyn Write, row: &Row<'_>) -> core::fmt::Result {
+        let range = row.range();
+        if range.start != 0 {
+            f.write_str(",")?;
+        }
+        f.write_fmt(format_args!(r#"{{"offset":{},"hex":""#, row.offset()))?;
+        (0..range.len()).try_for_each(|i| row.write_byte(f, i))?;
+        f.write_str(r#"","text":""#)?;
+        row.write_text(&mut Escape(f))?;
+        f.write_str(r#"","annotations":["#)?;
+        let regions = self.regions.iter().filter(|region| region.overlaps(&range));
+        for (i, region) in regions.enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            f.write_fmt(format_args!(
+                r#"{{"start":{},"end":{},"label":""#,
+                self.base_offset.wrapping_add(region.range.start),
+                self.base_offset.wrapping_add(region.range.end),
+            ))?;
+            Escape(f).write_str(region.label)?;
+            f.write_str(r#""}"#)?;
+        }
+        f.write_str("]}")
+    }
+
+    fn squeezed(&self, f: &mut dyn Write, first: &Row<'_>, count: usize) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            r#",{{"offset":{},"repeats":{}}}"#,
+            first.offset(),
+            count
+        ))
+    }
+
+    fn end(&self, f: &mut dyn Write) -> core::fmt::Result {
+        f.write_str("]}")
+    }
+}
+
+/// The regions of [`Dump::annotate`] as a JSON array, a sidecar for tools that
+/// need the structure of the bytes instead of their rendering.
+///
+/// Created by [`Dump::annotations_json`]. Each region is an object with its
+/// `offset`, including the [`base_offset`](crate::DumpConfig::base_offset),
+/// `len`, `label` and its bytes as `value` in hex digits.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig, Region};
+///
+/// let buf = Buf(*b"\x7FELF\x02\x01");
+/// let regions = [Region::new(0, 4, "magic"), Region::new(4, 1, "class")];
+/// let config = DumpConfig::portable().base_offset(0x100).uppercase(false);
+/// let dump = buf.display_with(&config).annotate(&regions);
+/// assert_eq!(format!("{}", dump.annotations_json()), concat!(
+///     r#"[{"offset":256,"len":4,"label":"magic","value":"7f454c46"},"#,
+///     r#"{"offset":260,"len":1,"label":"class","value":"02"}]"#,
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Annotations<'a> {
+    dump: Dump<'a>,
+    regions: &'a [Region<'a>],
+}
+
+impl<'a> Annotations<'a> {
+    pub(crate) fn new(json: Json<'a>) -> Self {
+        Self {
+            dump: json.dump,
+            regions: json.regions,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (bytes, config) = (self.dump.bytes(), self.dump.config());
+        f.write_str("[")?;
+        for (i, region) in self.regions.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+            let range = region.range.start..core::cmp::min(region.range.end, bytes.len());
+            f.write_fmt(format_args!(
+                r#"{{"offset":{},"len":{},"label":""#,
+                config.base_offset.wrapping_add(region.range.start),
+                region.range.len(),
+            ))?;
+            Escape(f).write_str(region.label)?;
+            f.write_str(r#"","value":""#)?;
+            for i in range {
+                self.dump.write_byte(f, i, bytes[i])?;
+            }
+            f.write_str(r#""}"#)?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+impl Debug for Annotations<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Annotations::fmt(self, f)
+    }
+}
+
+impl Display for Annotations<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Annotations::fmt(self, f)
+    }
+}
+
+/// Escapes the text for a JSON string.
+struct Escape<'w, W: ?Sized>(&'w mut W);
+
+impl<W: Write + ?Sized> Write for Escape<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            match char {
+                '"' => self.0.write_str(r#"\""#)?,
+                '\\' => self.0.write_str(r"\\")?,
+                '\n' => self.0.write_str(r"\n")?,
+                '\r' => self.0.write_str(r"\r")?,
+                '\t' => self.0.write_str(r"\t")?,
+                '\0'..='\x1F' => self.0.write_fmt(format_args!(r"\u{:04x}", char as u32))?,
+                char => self.0.write_char(char)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,13 +3,18 @@
 //! Memdbg provides the [`Buf`] struct which implements Debug
 //! to provide a peak into memory akin to hex readers.
 //!
-//! The [`buf_dbg`] macro extends this view to any struct.
+//! The [`buf_dbg`] macro extends this view to any struct, [`buf_defmt`]
+//! sends it over defmt with the `defmt` feature.
+//!
+//! The layout can be customized with a [`DumpConfig`], see [`Buf::display_with`].
+//! The default layout depends on the target and the address of the buffer,
+//! [`DumpConfig::portable`] prints the same on every target.
 //!
 //! ## Example
 //!
 //! ```rust
 //! let buf = memdbg::Buf(*b"\x41 \x68 \x90 \x00 \x2f This is a test string, The Line Break will demonstrate multiline formatting!");
-//! dbg!("{buf:?}");
+//! println!("{buf:#?}");
 //! ```
 //! will display
 //! ```text
@@ -17,68 +22,733 @@
 //! | 20 54 68 65 20 4C 69 6E | 65 20 42 72 65 61 6B 20 | 77 69 6C 6C 20 64 65 6D | 6F 6E 73 74 72 61 74 65 | .The.Line.Break.will.demonstrate
 //! | 20 6D 75 6C 74 69 6C 69 | 6E 65 20 66 6F 72 6D 61 | 74 74 69 6E 67 21                                 | .multiline.formatting!
 //! ```
+//!
+//! `{buf:?}` and `{buf}` print the bytes on a single line instead, `41 20 68 20 90 …`.
 
 #![no_std]
 
-use core::{
-    cmp::min,
-    fmt::{Debug, Formatter},
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod capture;
+#[cfg(feature = "color")]
+mod color;
+mod compare;
+mod compat;
+mod config;
+mod csv;
+mod dump;
+#[cfg(feature = "std")]
+mod elf;
+mod export;
+mod format;
+mod highlight;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(feature = "json")]
+mod json;
+mod markdown;
+mod mmio;
+mod parse;
+mod snapshot;
+mod sparse;
+mod stream;
+#[cfg(feature = "std")]
+mod terminal;
+mod text;
+mod value;
+mod yaml;
+
+use core::array::TryFromSliceError;
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt::{Binary, Debug, Display, Formatter, LowerHex, UpperHex, Write};
+use core::ops::{Deref, DerefMut, Index, IndexMut};
+use core::slice::SliceIndex;
+use core::str::FromStr;
+
+pub use capture::Capture;
+pub use compare::SideBySide;
+pub use compat::{Gdb, GdbFormat, HexdumpC, Od, OdRadix, Wireshark, Xxd};
+#[cfg(feature = "color")]
+pub use config::Shading;
+pub use config::{Checksum, DumpConfig, Endian, Notation, Offsets, Radix, Values};
+pub use csv::Csv;
+pub use dump::{Dump, LinePrefix, TooSmall};
+#[cfg(feature = "std")]
+pub use elf::{CoreSegment, ElfCore};
+#[cfg(feature = "std")]
+pub use export::Pcap;
+pub use export::{
+    Base64, CArray, IntelHex, QuotedPrintable, RustLiteral, Srec, SrecFormat, Uuencode,
 };
+pub use format::{DumpFormat, Formatted, Row};
+pub use highlight::{Highlight, Region, Style};
+#[cfg(feature = "html")]
+pub use html::Html;
+#[cfg(feature = "json")]
+pub use json::{Annotations, Json};
+pub use markdown::Markdown;
+pub use mmio::Volatile;
+pub use parse::ParseError;
+pub use snapshot::{Snapshot, SnapshotError};
+pub use sparse::Sparse;
+pub use stream::{Dumper, IterDump};
+pub use text::Charset;
+pub use value::{dump_value, Elements, NoUninit, Uninit};
+pub use yaml::Yaml;
 
 /// A buffer of `N` bytes, a `[u8; N]` with a nice Debug impl
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 #[repr(C)]
 pub struct Buf<const N: usize>(pub [u8; N]);
 
+impl<const N: usize> Buf<N> {
+    /// A buffer of zeros, e.g. a scratch buffer.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// const SCRATCH: Buf<4> = Buf::zeroed();
+    /// assert_eq!(SCRATCH, Buf([0; 4]));
+    /// ```
+    pub const fn zeroed() -> Self {
+        Self([0; N])
+    }
+
+    /// A buffer of `byte` repeated, e.g. erased flash with `0xFF`.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// assert_eq!(Buf::<3>::filled(0xFF), Buf([0xFF; 3]));
+    /// ```
+    pub const fn filled(byte: u8) -> Self {
+        Self([byte; N])
+    }
+
+    /// A buffer with the byte at each index returned by `f`, e.g. a test
+    /// pattern.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// assert_eq!(Buf::from_fn(|i| i as u8 * 2), Buf([0, 2, 4, 6]));
+    /// ```
+    pub fn from_fn(f: impl FnMut(usize) -> u8) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    /// The first `N` bytes of `bytes`, padded with zeros if it is shorter,
+    /// e.g. for a receive buffer of a fixed size.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// assert_eq!(Buf::<4>::from_slice_padded(b"ab"), Buf(*b"ab\0\0"));
+    /// assert_eq!(Buf::<2>::from_slice_padded(b"abcd"), Buf(*b"ab"));
+    /// ```
+    pub fn from_slice_padded(bytes: &[u8]) -> Self {
+        let mut buf = [0; N];
+        let len = core::cmp::min(N, bytes.len());
+        buf[..len].copy_from_slice(&bytes[..len]);
+        Self(buf)
+    }
+
+    /// The first `N` bytes of `bytes`, or `None` if it is shorter, e.g. for
+    /// the header of a packet.
+    ///
+    /// [`TryFrom`] converts slices of exactly `N` bytes.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// assert_eq!(Buf::<2>::from_slice_truncated(b"abcd"), Some(Buf(*b"ab")));
+    /// assert_eq!(Buf::<4>::from_slice_truncated(b"ab"), None);
+    /// assert!(Buf::<4>::try_from(&b"abcd"[..]).is_ok());
+    /// ```
+    pub fn from_slice_truncated(bytes: &[u8]) -> Option<Self> {
+        let bytes = bytes.get(..N)?;
+        Self::try_from(bytes).ok()
+    }
+
+    /// Splits the buffer after `M` bytes, e.g. into a header and its
+    /// payload. `M + R` has to be `N`, which is checked at compile time.
+    ///
+    /// ```rust
+    /// use memdbg::Buf;
+    ///
+    /// let frame = Buf(*b"\x01\x02payload");
+    /// let (header, payload): (Buf<2>, Buf<7>) = frame.split();
+    /// assert_eq!(header, Buf([1, 2]));
+    /// assert_eq!(header.concat(payload), frame);
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// let (header, payload): (memdbg::Buf<2>, memdbg::Buf<2>) = memdbg::Buf([0; 9]).split();
+    /// ```
+    pub fn split<const M: usize, const R: usize>(self) -> (Buf<M>, Buf<R>) {
+        const { assert!(M + R == N, "the lengths of the parts do not add up") };
+        let (left, right) = self.0.split_at(M);
+        (Buf::from_slice_padded(left), Buf::from_slice_padded(right))
+    }
+
+    /// Appends `other`, e.g. a payload to its header. `R` has to be `N + M`,
+    /// which is checked at compile time, see [`Buf::split`].
+    pub fn concat<const M: usize, const R: usize>(self, other: Buf<M>) -> Buf<R> {
+        const {
+            assert!(
+                N + M == R,
+                "the length of the result is not the sum of the lengths"
+            )
+        };
+        let mut buf = [0; R];
+        buf[..N].copy_from_slice(&self.0);
+        buf[N..].copy_from_slice(&other.0);
+        Buf(buf)
+    }
+
+    /// The bytes as a contiguous hex string, like `{:x}`, e.g. for an error
+    /// type or a log record.
+    ///
+    /// ```rust
+    /// assert_eq!(memdbg::Buf([0x41, 0x20, 0xAB]).to_hex_string(), "4120ab");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_hex_string(&self) -> alloc::string::String {
+        alloc::format!("{:x}", self)
+    }
+
+    /// The full hex dump, like `{:#?}`.
+    ///
+    /// ```rust
+    /// let buf = memdbg::Buf(*b"dump");
+    /// assert_eq!(buf.to_dump_string(), format!("{:#?}", buf));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_dump_string(&self) -> alloc::string::String {
+        alloc::format!("{:#?}", self)
+    }
+
+    /// Formats the buffer with a custom [`DumpConfig`] instead of the default layout.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let buf = Buf(*b"0123456789abcdefghijklmnopqrstuvwxyz");
+    /// assert_eq!(format!("{:?}", buf.display_with(&DumpConfig::portable().bytes_per_line(16))), concat!(
+    ///     "\n | 30 31 32 33 34 35 36 37 | 38 39 61 62 63 64 65 66 | 0123456789abcdef",
+    ///     "\n | 67 68 69 6A 6B 6C 6D 6E | 6F 70 71 72 73 74 75 76 | ghijklmnopqrstuv",
+    ///     "\n | 77 78 79 7A                                       | wxyz",
+    /// ));
+    /// ```
+    pub fn display_with(&self, config: &DumpConfig) -> Dump<'_> {
+        Dump::new(&self.0, config)
+    }
+
+    /// Parses a dump back into the buffer, e.g. to turn a dump from a log into
+    /// a test case.
+    ///
+    /// Besides the layouts of this crate, with offsets or addresses,
+    /// highlights and colors, it accepts `xxd` and plain hex strings like
+    /// `41 20 68`, `0x412068` or `41, 20, 68`. The character column and
+    /// blank lines are ignored. Words of several bytes are read in the order
+    /// they are written.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig, ParseError};
+    ///
+    /// let buf = Buf(*b"0123456789abcdefghi");
+    /// let dump = format!("{:?}", buf.display_with(&DumpConfig::canonical().bytes_per_line(8)));
+    /// assert_eq!(Buf::parse(&dump), Ok(buf));
+    /// assert_eq!(Buf::parse("00000000: 3031 3233  0123"), Ok(Buf(*b"0123")));
+    /// assert_eq!("0x4120, 68".parse(), Ok(Buf(*b"A h")));
+    /// assert_eq!(Buf::<2>::parse("41 20 68"), Err(ParseError::Length { expected: 2, found: 3 }));
+    /// assert_eq!(Buf::<2>::parse("41\n2x"), Err(ParseError::InvalidDigit { line: 2 }));
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut buf = [0; N];
+        let mut bytes = parse::Bytes {
+            buf: &mut buf,
+            len: 0,
+        };
+        parse::parse(s, &mut bytes)?;
+        if bytes.len != N {
+            return Err(ParseError::Length {
+                expected: N,
+                found: bytes.len,
+            });
+        }
+
+        Ok(Self(buf))
+    }
+
+    /// Writes the buffer with `snapshot` as its header into `out` and returns
+    /// the length of the snapshot, [`Snapshot::HEADER_LEN`] + `N`.
+    ///
+    /// Unlike a text dump, [`Buf::from_snapshot`] restores the exact bytes
+    /// along with their address and byte order, e.g. to dump captures from a
+    /// device later.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Snapshot};
+    ///
+    /// let buf = Buf(*b"captured");
+    /// let mut out = [0; 64];
+    /// let len = buf.to_snapshot(&Snapshot::new(0x2000_0000), &mut out)?;
+    /// let (restored, snapshot) = Buf::<8>::from_snapshot(&out[..len])?;
+    /// assert_eq!(restored, buf);
+    /// assert_eq!(snapshot.base_address, 0x2000_0000);
+    /// println!("{:?}", restored.display_with(&snapshot.config()));
+    /// # Ok::<(), memdbg::SnapshotError>(())
+    /// ```
+    pub fn to_snapshot(&self, snapshot: &Snapshot, out: &mut [u8]) -> Result<usize, SnapshotError> {
+        snapshot.write(&self.0, out)
+    }
+
+    /// Reads a snapshot written by [`Buf::to_snapshot`], returning the buffer
+    /// and its header.
+    pub fn from_snapshot(snapshot: &[u8]) -> Result<(Self, Snapshot), SnapshotError> {
+        let (header, bytes) = Snapshot::read(snapshot)?;
+        let Ok(buf) = bytes.try_into() else {
+            return Err(SnapshotError::Length {
+                expected: Snapshot::HEADER_LEN + N,
+                found: snapshot.len(),
+            });
+        };
+
+        Ok((Self(buf), header))
+    }
+}
+
+/// A buffer of zeros, see [`Buf::zeroed`].
+impl<const N: usize> Default for Buf<N> {
+    fn default() -> Self {
+        Self::zeroed()
+    }
+}
+
+/// Copies a slice of exactly `N` bytes, see [`Buf::from_slice_padded`] and
+/// [`Buf::from_slice_truncated`] for other lengths.
+impl<const N: usize> TryFrom<&[u8]> for Buf<N> {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        bytes.try_into().map(Self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize> From<Buf<N>> for alloc::vec::Vec<u8> {
+    fn from(buf: Buf<N>) -> Self {
+        buf.0.into()
+    }
+}
+
+/// Converts a `Vec` of exactly `N` bytes, returning it otherwise.
+///
+/// ```rust
+/// use memdbg::Buf;
+///
+/// assert_eq!(Buf::try_from(vec![1, 2]), Ok(Buf([1, 2])));
+/// assert_eq!(Buf::<2>::try_from(vec![1]), Err(vec![1]));
+/// assert_eq!(Vec::from(Buf([1, 2])), [1, 2]);
+/// ```
+#[cfg(feature = "alloc")]
+impl<const N: usize> TryFrom<alloc::vec::Vec<u8>> for Buf<N> {
+    type Error = alloc::vec::Vec<u8>;
+
+    fn try_from(bytes: alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
+        bytes.try_into().map(Self)
+    }
+}
+
+/// Derefs to the bytes, so a buffer can be used like the array it wraps.
+///
+/// ```rust
+/// let mut buf = memdbg::Buf(*b"abcd");
+/// buf[0] = b'A';
+/// buf[1..].make_ascii_uppercase();
+/// assert_eq!(buf.len(), 4);
+/// assert_eq!(&buf[..], b"ABCD");
+/// assert_eq!(buf.into_iter().rev().next(), Some(b'D'));
+/// ```
+impl<const N: usize> Deref for Buf<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> DerefMut for Buf<N> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Buf<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for Buf<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> Borrow<[u8]> for Buf<N> {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> BorrowMut<[u8]> for Buf<N> {
+    fn borrow_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<I: SliceIndex<[u8]>, const N: usize> Index<I> for Buf<N> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        &self.0[index]
+    }
+}
+
+impl<I: SliceIndex<[u8]>, const N: usize> IndexMut<I> for Buf<N> {
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize> IntoIterator for Buf<N> {
+    type Item = u8;
+    type IntoIter = core::array::IntoIter<u8, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a Buf<N> {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a mut Buf<N> {
+    type Item = &'a mut u8;
+    type IntoIter = core::slice::IterMut<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+/// Parses a dump, see [`Buf::parse`].
+impl<const N: usize> FromStr for Buf<N> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// `{:#?}` displays the full hex dump, `{:?}` a compact single line that
+/// fits into the Debug output of surrounding types.
+///
+/// The precision limits the number of bytes displayed. The width pads the
+/// compact form and indents each line of the full dump, e.g. to nest it in
+/// other output.
+///
+/// ```rust
+/// let buf = memdbg::Buf(*b"\x41 \x68");
+/// assert_eq!(format!("{buf:?}"), "41 20 68");
+/// assert_eq!(format!("{buf:.2?}"), "41 20");
+/// assert_eq!(format!("{buf:>10?}"), "  41 20 68");
+/// ```
 impl<const N: usize> Debug for Buf<N> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        let align = core::mem::align_of::<usize>();
-        let chunks_per_line = 32 / align;
-        let ptr = self.0.as_ptr();
-        let offset = ptr.align_offset(align);
-        let offset = min(offset, self.0.len());
-        let (pre, rest) = self.0.split_at(offset);
-        for &char in pre {
-            f.write_fmt(format_args!(" {:02X}", char))?;
-        }
-        if !pre.is_empty() {
-            f.write_str(" |")?;
-        }
-        write_ascii(f, pre)?;
-        for line in rest.chunks(align * chunks_per_line) {
-            f.write_str("\n")?;
-            for bytes in line.chunks(align) {
-                f.write_str(" |")?;
-                for &char in bytes {
-                    f.write_fmt(format_args!(" {:02X}", char))?;
-                }
-            }
+        Debug::fmt(&HexDump(&self.0), f)
+    }
+}
 
-            let fill = align * chunks_per_line - line.len();
-            let fill = 3 * fill + 2 * (fill / align);
-            for _ in 0..fill {
-                f.write_str(" ")?;
-            }
+/// `{}` displays the same compact single line as `{:?}`, e.g. for error
+/// messages, and `{:#}` the full hex dump.
+///
+/// ```rust
+/// let buf = memdbg::Buf(*b"\x41 \x68");
+/// assert_eq!(format!("unexpected bytes: {buf}"), "unexpected bytes: 41 20 68");
+/// ```
+impl<const N: usize> Display for Buf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&HexDump(&self.0), f)
+    }
+}
+
+/// `{:x}` displays the bytes as a contiguous hex string, `{:#x}` prefixed with `0x`.
+///
+/// ```rust
+/// let buf = memdbg::Buf([0x41, 0x20, 0xAB]);
+/// assert_eq!(format!("{buf:x}"), "4120ab");
+/// assert_eq!(format!("{buf:#X}"), "0x4120AB");
+/// ```
+impl<const N: usize> LowerHex for Buf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        LowerHex::fmt(&HexDump(&self.0), f)
+    }
+}
+
+/// `{:X}` displays the bytes as a contiguous hex string, see [`LowerHex`].
+impl<const N: usize> UpperHex for Buf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        UpperHex::fmt(&HexDump(&self.0), f)
+    }
+}
+
+/// `{:b}` displays the bytes as a contiguous bit string, `{:#b}` prefixed with `0b`.
+///
+/// ```rust
+/// let buf = memdbg::Buf([0x81, 0x05]);
+/// assert_eq!(format!("{buf:b}"), "1000000100000101");
+/// ```
+impl<const N: usize> Binary for Buf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Binary::fmt(&HexDump(&self.0), f)
+    }
+}
+
+/// Sends the raw bytes over the defmt transport, so the microcontroller
+/// does not format the dump.
+///
+/// The host decodes them as a list, e.g. `[65, 32, 104]`, and renders the
+/// hex table from a `Buf` of these bytes with `{:#?}`.
+///
+/// ```rust
+/// let buf = memdbg::Buf(*b"\x41 \x68");
+/// defmt::info!("rx: {}", buf);
+/// ```
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for Buf<N> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=[u8]}", &self.0[..]);
+    }
+}
+
+/// A borrowed byte slice with the same formatting as [`Buf`], e.g. for
+/// receive buffers, `Vec<u8>` or the bytes of a `&str`.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, HexDump};
+///
+/// let packet = vec![0x41, 0x20, 0x68];
+/// assert_eq!(format!("{:?}", HexDump(&packet)), "41 20 68");
+/// assert_eq!(format!("{:x}", HexDump("hi".as_bytes())), "6869");
+/// assert_eq!(format!("{:?}", HexDump(b"0123456789abcdef").display_with(&DumpConfig::portable().bytes_per_line(8))), concat!(
+///     "\n | 30 31 32 33 34 35 36 37 | 01234567",
+///     "\n | 38 39 61 62 63 64 65 66 | 89abcdef",
+/// ));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexDump<'a>(pub &'a [u8]);
+
+impl<'a> HexDump<'a> {
+    /// Formats the bytes with a custom [`DumpConfig`], see [`Buf::display_with`].
+    pub fn display_with(&self, config: &DumpConfig) -> Dump<'a> {
+        Dump::new(self.0, config)
+    }
+}
+
+/// `{:#?}` displays the full hex dump, `{:?}` a compact single line, like [`Buf`].
+impl Debug for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fmt_buf(f, Dump::new(self.0, &DumpConfig::new()))
+    }
+}
 
-            f.write_str(" | ")?;
+/// The compact single line, `{:#}` the full hex dump, like [`Buf`].
+impl Display for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fmt_buf(f, Dump::new(self.0, &DumpConfig::new()))
+    }
+}
 
-            write_ascii(f, line)?;
+/// `{:x}` displays the bytes as a contiguous hex string, like [`Buf`].
+impl LowerHex for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
         }
+        self.0
+            .iter()
+            .try_for_each(|byte| f.write_fmt(format_args!("{:02x}", byte)))
+    }
+}
 
-        Ok(())
+/// `{:X}` displays the bytes as a contiguous hex string, like [`Buf`].
+impl UpperHex for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        self.0
+            .iter()
+            .try_for_each(|byte| f.write_fmt(format_args!("{:02X}", byte)))
     }
 }
 
-fn write_ascii(f: &mut Formatter, buf: &[u8]) -> core::fmt::Result {
-    for &char in buf {
-        if char.is_ascii_graphic() {
-            f.write_fmt(format_args!("{}", char as char))?;
-        } else {
-            f.write_str(".")?;
+/// `{:b}` displays the bytes as a contiguous bit string, like [`Buf`].
+impl Binary for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            f.write_str("0b")?;
         }
+        self.0
+            .iter()
+            .try_for_each(|byte| f.write_fmt(format_args!("{:08b}", byte)))
+    }
+}
+
+/// Dumps anything that is a byte slice, e.g. `Vec<u8>`, arrays, `str` or
+/// the `Bytes` of other crates.
+///
+/// ```rust
+/// use memdbg::MemDump;
+///
+/// let packet = vec![0x41, 0x20, 0x68];
+/// assert_eq!(format!("{:?}", packet.hex_dump()), "41 20 68");
+/// assert_eq!(format!("{:?}", "hi".hex_dump()), "68 69");
+/// println!("{:#?}", [0x5A; 40].hex_dump());
+/// ```
+pub trait MemDump {
+    /// The bytes with the formatting of [`Buf`].
+    fn hex_dump(&self) -> HexDump<'_>;
+}
+
+impl<T: AsRef<[u8]> + ?Sized> MemDump for T {
+    fn hex_dump(&self) -> HexDump<'_> {
+        HexDump(self.as_ref())
+    }
+}
+
+/// Dumps `len` bytes at `ptr` that are not behind a slice, e.g. foreign
+/// allocations, memory-mapped regions or addresses reported by a crash
+/// handler.
+///
+/// The dump has the formatting of [`Buf`], with [`HexDump::display_with`]
+/// for a custom [`DumpConfig`]. The bytes are read whenever it is formatted.
+///
+/// ```rust
+/// let bytes = *b"foreign";
+/// let dump = unsafe { memdbg::dump_raw(bytes.as_ptr(), bytes.len()) };
+/// assert_eq!(format!("{:?}", dump), "66 6F 72 65 69 67 6E");
+/// ```
+///
+/// # Safety
+///
+/// As for [`core::slice::from_raw_parts`], for as long as the dump is used:
+///
+/// - `ptr` is non-null, and the `len` bytes at it are readable memory of a
+///   single allocation, even for a `len` of zero.
+/// - The bytes are initialized and not written to, e.g. by another thread or
+///   by hardware. Registers of peripherals are dumped with [`Volatile`].
+/// - `len` is at most `isize::MAX`.
+pub unsafe fn dump_raw<'a>(ptr: *const u8, len: usize) -> HexDump<'a> {
+    // SAFETY: guaranteed by the caller
+    HexDump(unsafe { core::slice::from_raw_parts(ptr, len) })
+}
+
+/// Writes the dump of `bytes` into `w` without a [`Formatter`], see
+/// [`Dump::write_to`].
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use memdbg::DumpConfig;
+///
+/// /// A serial port, written character by character.
+/// struct Uart;
+///
+/// impl Write for Uart {
+///     fn write_str(&mut self, s: &str) -> core::fmt::Result {
+///         s.bytes().for_each(|byte| { /* write the byte into the data register */ });
+///         Ok(())
+///     }
+/// }
+///
+/// memdbg::write_dump_to(&mut Uart, b"received", &DumpConfig::new())?;
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+pub fn write_dump_to<W: Write + ?Sized>(
+    w: &mut W,
+    bytes: &[u8],
+    config: &DumpConfig,
+) -> core::fmt::Result {
+    Dump::new(bytes, config).write_to(w)
+}
+
+/// Writes the dump of `bytes` to `w`, e.g. a file, a socket or stderr,
+/// without rendering it into a `String` first.
+///
+/// The dump is written in small pieces, unbuffered writers like files are
+/// best wrapped in a [`BufWriter`](std::io::BufWriter).
+///
+/// ```rust
+/// use memdbg::{DumpConfig, HexDump};
+///
+/// let (bytes, config) = ([0x5A; 4096], DumpConfig::new());
+/// let mut file = Vec::new();
+/// memdbg::dump_to_writer(&bytes, &mut file, &config)?;
+/// assert_eq!(file, format!("{:?}", HexDump(&bytes).display_with(&config)).into_bytes());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub fn dump_to_writer<W: std::io::Write + ?Sized>(
+    bytes: &[u8],
+    w: &mut W,
+    config: &DumpConfig,
+) -> std::io::Result<()> {
+    let mut io = Io {
+        inner: w,
+        error: None,
+    };
+    write_dump_to(&mut io, bytes, config).map_err(|_| match io.error {
+        Some(error) => error,
+        None => std::io::Error::other("formatting the dump failed"),
+    })
+}
+
+/// Writes to an [`std::io::Write`], keeping the error that `fmt::Write`
+/// cannot return.
+#[cfg(feature = "std")]
+struct Io<'w, W: ?Sized> {
+    inner: &'w mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for Io<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            core::fmt::Error
+        })
     }
+}
 
-    Ok(())
+fn fmt_buf(f: &mut Formatter<'_>, dump: Dump<'_>) -> core::fmt::Result {
+    if f.alternate() {
+        dump.fmt_table(f)
+    } else {
+        dump.fmt_compact(f)
+    }
 }
 
 #[doc(hidden)]
@@ -88,7 +758,7 @@ pub fn dbg_impl<T, const N: usize>(
     t: &T,
     _name: &str,
 ) -> ::core::fmt::Result {
-    <Buf<N> as Debug>::fmt(unsafe { core::mem::transmute(t) }, f)
+    <Buf<N> as Debug>::fmt(unsafe { core::mem::transmute::<&T, &Buf<N>>(t) }, f)
 }
 
 #[doc(hidden)]
@@ -101,15 +771,115 @@ pub fn dbg_impl<T, const N: usize>(
     f.write_str(name)
 }
 
+/// Formats only the given `(offset, size)` fields of `t`, the remaining
+/// bytes are padding and never read.
+#[doc(hidden)]
+#[cfg(feature = "debug")]
+pub fn padded_dbg_impl<T, const N: usize>(
+    f: &mut ::core::fmt::Formatter<'_>,
+    t: &T,
+    _name: &str,
+    fields: &[(usize, usize)],
+) -> ::core::fmt::Result {
+    let ptr = (t as *const T).cast::<u8>();
+    let mut bytes = [0; N];
+    let mut padding = [true; N];
+    for &(offset, size) in fields {
+        // SAFETY: the fields of `t` are initialized and within its size
+        unsafe {
+            core::ptr::copy_nonoverlapping(ptr.add(offset), bytes.as_mut_ptr().add(offset), size)
+        };
+        padding[offset..offset + size].fill(false);
+    }
+
+    let dump = Dump::new(&bytes, &DumpConfig::new())
+        .with_addr(ptr as usize)
+        .padding(&padding);
+    fmt_buf(f, dump)
+}
+
+#[doc(hidden)]
+#[cfg(all(not(feature = "debug"), feature = "stringify"))]
+pub fn padded_dbg_impl<T, const N: usize>(
+    f: &mut ::core::fmt::Formatter<'_>,
+    _t: &T,
+    name: &str,
+    _fields: &[(usize, usize)],
+) -> ::core::fmt::Result {
+    f.write_str(name)
+}
+
+#[doc(hidden)]
+#[cfg(feature = "defmt")]
+pub fn defmt_impl<T, const N: usize>(f: defmt::Formatter<'_>, t: &T) {
+    defmt::Format::format(unsafe { core::mem::transmute::<&T, &Buf<N>>(t) }, f)
+}
+
+/// Sends only the given `(offset, size)` fields of `t`, the remaining
+/// bytes are padding and sent as zeros.
+#[doc(hidden)]
+#[cfg(feature = "defmt")]
+pub fn padded_defmt_impl<T, const N: usize>(
+    f: defmt::Formatter<'_>,
+    t: &T,
+    fields: &[(usize, usize)],
+) {
+    let ptr = (t as *const T).cast::<u8>();
+    let mut bytes = [0; N];
+    for &(offset, size) in fields {
+        // SAFETY: the fields of `t` are initialized and within its size
+        unsafe {
+            core::ptr::copy_nonoverlapping(ptr.add(offset), bytes.as_mut_ptr().add(offset), size)
+        };
+    }
+    defmt::Format::format(&Buf(bytes), f)
+}
+
+#[doc(hidden)]
+pub const fn field_size<T, F>(_: fn(&T) -> &F) -> usize {
+    core::mem::size_of::<F>()
+}
+
 /// Implements Debug for a type.
 ///
 /// Feature `debug` formats it as if it would be a [`Buf`].
 /// Otherwise, if feature `stringify` is specified, it displays the type name.
 /// If none of those features are specified, this macro does nothing and does
 /// not implement Debug.
+///
+/// Listing the fields of a struct displays its padding bytes as `--`,
+/// without reading them:
+///
+/// ```rust
+/// #[repr(C)]
+/// struct Header {
+///     kind: u8,
+///     len: u32,
+/// }
+/// memdbg::buf_dbg!(Header { kind, len });
+///
+/// let header = Header { kind: 1, len: 2 };
+/// # #[cfg(feature = "debug")]
+/// assert_eq!(format!("{header:?}"), "01 -- -- -- 02 00 00 00");
+/// ```
 #[macro_export]
 #[cfg(any(feature = "debug", feature = "stringify"))]
 macro_rules! buf_dbg {
+    ($name:ty { $($field:tt),* $(,)? }) => {
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::memdbg::padded_dbg_impl::<Self, { core::mem::size_of::<Self>() }>(
+                    f,
+                    self,
+                    stringify!($name),
+                    &[$((
+                        ::core::mem::offset_of!(Self, $field),
+                        ::memdbg::field_size(|s: &Self| &s.$field),
+                    )),*],
+                )
+            }
+        }
+    };
     ($name:ty) => {
         impl ::core::fmt::Debug for $name {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
@@ -127,5 +897,54 @@ macro_rules! buf_dbg {
 #[macro_export]
 #[cfg(not(any(feature = "debug", feature = "stringify")))]
 macro_rules! buf_dbg {
-    ($name:ty) => {};
+    ($name:ty $({ $($field:tt),* $(,)? })?) => {};
+}
+
+/// Implements `defmt::Format` for a type, sending its bytes like a [`Buf`].
+///
+/// The crate using it depends on `defmt` as well. Listing the fields of a
+/// struct sends its padding bytes as zeros, without reading them. Without
+/// the `defmt` feature this macro does nothing.
+///
+/// ```rust
+/// #[repr(C)]
+/// struct Header {
+///     kind: u8,
+///     len: u32,
+/// }
+/// memdbg::buf_defmt!(Header { kind, len });
+///
+/// defmt::info!("{}", Header { kind: 1, len: 2 });
+/// ```
+#[macro_export]
+#[cfg(feature = "defmt")]
+macro_rules! buf_defmt {
+    ($name:ty { $($field:tt),* $(,)? }) => {
+        impl ::defmt::Format for $name {
+            fn format(&self, f: ::defmt::Formatter<'_>) {
+                ::memdbg::padded_defmt_impl::<Self, { core::mem::size_of::<Self>() }>(
+                    f,
+                    self,
+                    &[$((
+                        ::core::mem::offset_of!(Self, $field),
+                        ::memdbg::field_size(|s: &Self| &s.$field),
+                    )),*],
+                )
+            }
+        }
+    };
+    ($name:ty) => {
+        impl ::defmt::Format for $name {
+            fn format(&self, f: ::defmt::Formatter<'_>) {
+                ::memdbg::defmt_impl::<Self, { core::mem::size_of::<Self>() }>(f, self)
+            }
+        }
+    };
+}
+
+/// Doesn't do anything without the `defmt` feature.
+#[macro_export]
+#[cfg(not(feature = "defmt"))]
+macro_rules! buf_defmt {
+    ($name:ty $({ $($field:tt),* $(,)? })?) => {};
 }
@@ -0,0 +1,172 @@
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::{Dump, DumpFormat, Formatted, Row};
+
+/// A hex dump that renders in Markdown, e.g. in issues and pull requests.
+///
+/// Created by [`Dump::markdown`]. By default the dump is written in a
+/// fenced code block, [`Markdown::table`] writes a table instead.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig};
+///
+/// let buf = Buf(*b"| *md* |");
+/// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(8));
+/// assert_eq!(format!("{}", dump.markdown()), concat!(
+///     "```text\n",
+///     " | 7C 20 2A 6D 64 2A 20 7C | |.*md*.|\n",
+///     "```",
+/// ));
+/// assert_eq!(format!("{}", dump.markdown().table(true)), concat!(
+///     "| Offset | 00 | 01 | 02 | 03 | 04 | 05 | 06 | 07 | Text |\n",
+///     "| -----: | -- | -- | -- | -- | -- | -- | -- | -- | :--- |\n",
+///     r"| 0000 | 7C | 20 | 2A | 6D | 64 | 2A | 20 | 7C | \|\.\*md\*\.\| |",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Markdown<'a> {
+    dump: Dump<'a>,
+    table: bool,
+}
+
+impl<'a> Markdown<'a> {
+    pub(crate) fn new(dump: Dump<'a>) -> Self {
+        Self { dump, table: false }
+    }
+
+    /// Writes a table with a column per byte of a line instead of a code
+    /// block, with the offsets and characters of each line.
+    ///
+    /// The lines are split like those of a [`DumpFormat`], lines collapsed
+    /// by [`squeeze`](crate::DumpConfig::squeeze) are a row with `*` and
+    /// their number.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().bytes_per_line(2).squeeze(true);
+    /// let dump = Buf([0; 6]).display_with(&config);
+    /// assert_eq!(format!("{}", dump.markdown().table(true)), concat!(
+    ///     "| Offset | 00 | 01 | Text |\n",
+    ///     "| -----: | -- | -- | :--- |\n",
+    ///     r"| 0000 | 00 | 00 | \.\. |", "\n",
+    ///     r"| \* |  |  | 2 identical lines |",
+    /// ));
+    /// ```
+    pub fn table(mut self, enabled: bool) -> Self {
+        self.table = enabled;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.table {
+            let table = Table {
+                bytes_per_line: self.dump.config().bytes_per_line,
+                uppercase: self.dump.config().uppercase,
+            };
+            return Display::fmt(&Formatted::new(self.dump, table), f);
+        }
+
+        // the fence has to be longer than any run of backticks in the dump
+        let mut backticks = Backticks { run: 0, longest: 0 };
+        self.dump.write(&mut backticks)?;
+        let fence = core::cmp::max(3, backticks.longest + 1);
+        (0..fence).try_for_each(|_| f.write_char('`'))?;
+        f.write_str("text\n")?;
+        self.dump.write(f)?;
+        f.write_str("\n")?;
+        (0..fence).try_for_each(|_| f.write_char('`'))
+    }
+}
+
+impl Debug for Markdown<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Markdown::fmt(self, f)
+    }
+}
+
+impl Display for Markdown<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Markdown::fmt(self, f)
+    }
+}
+
+/// Writes the rows of a [`Markdown::table`].
+struct Table {
+    bytes_per_line: usize,
+    uppercase: bool,
+}
+
+impl DumpFormat for Table {
+    fn begin(&self, f: &mut dyn Write, bytes_per_line: usize) -> core::fmt::Result {
+        f.write_str("| Offset |")?;
+        for column in 0..bytes_per_line {
+            match self.uppercase {
+                true => f.write_fmt(format_args!(" {:02X} |", column))?,
+                false => f.write_fmt(format_args!(" {:02x} |", column))?,
+            }
+        }
+        f.write_str(" Text |\n| -----: |")?;
+        (0..bytes_per_line).try_for_each(|_| f.write_str(" -- |"))?;
+        f.write_str(" :--- |")
+    }
+
+    fn row(&self, f: &mut dyn Write, row: &Row<'_>) -> core::fmt::Result {
+        match self.uppercase {
+            true => f.write_fmt(format_args!("\n| {:04X} |", row.offset()))?,
+            false => f.write_fmt(format_args!("\n| {:04x} |", row.offset()))?,
+        }
+        for i in 0..self.bytes_per_line {
+            f.write_str(" ")?;
+            if i < row.bytes().len() {
+                row.write_byte(&mut Escape(f), i)?;
+            }
+            f.write_str(" |")?;
+        }
+        f.write_str(" ")?;
+        row.write_text(&mut Escape(f))?;
+        f.write_str(" |")
+    }
+
+    fn squeezed(&self, f: &mut dyn Write, _: &Row<'_>, count: usize) -> core::fmt::Result {
+        f.write_str("\n| \\* |")?;
+        (0..self.bytes_per_line).try_for_each(|_| f.write_str("  |"))?;
+        match count {
+            1 => f.write_str(" 1 identical line |"),
+            _ => f.write_fmt(format_args!(" {} identical lines |", count)),
+        }
+    }
+}
+
+/// Measures the longest run of backticks.
+struct Backticks {
+    run: usize,
+    longest: usize,
+}
+
+impl Write for Backticks {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            self.run = if char == '`' { self.run + 1 } else { 0 };
+            self.longest = core::cmp::max(self.longest, self.run);
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes the ASCII punctuation, which may have a meaning in Markdown.
+struct Escape<'w, W: ?Sized>(&'w mut W);
+
+impl<W: Write + ?Sized> Write for Escape<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for char in s.chars() {
+            if char.is_ascii_punctuation() {
+                self.0.write_char('\\')?;
+            }
+            self.0.write_char(char)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Hex dumps of memory-mapped registers.
+
+use core::cmp::min;
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::dump::Indent;
+use crate::{DumpConfig, Dumper};
+
+/// A hex dump of memory read with volatile reads, e.g. the register block of
+/// a peripheral.
+///
+/// Each register is read once whenever the dump is formatted, with a single
+/// read of the access width, so the compiler neither elides nor merges the
+/// reads, and 32 bit registers are not torn into byte reads. The lines are
+/// written like those of a [`Dumper`], at most 256 bytes long, and
+/// [`Offsets::Address`](crate::Offsets::Address) shows the addresses of the
+/// registers. Set the [`base_offset`](DumpConfig::base_offset) to the bus
+/// address of the block to show that instead and the
+/// [`word_size`](DumpConfig::word_size) to the access width to show the
+/// registers as words.
+///
+/// The width of the formatter indents the lines and its precision limits
+/// the number of bytes like those of a [`Dump`](crate::Dump), the registers
+/// after it are not read.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, Endian, Offsets, Volatile};
+///
+/// let registers = [0x8000_0001_u32, 0x0000_00FF];
+/// let config = DumpConfig::portable()
+///     .bytes_per_line(8)
+///     .word_size(4)
+///     .endian(Endian::Native)
+///     .offsets(Offsets::Hex(8))
+///     .base_offset(0x4000_1000);
+/// // SAFETY: the registers are readable and aligned for 32 bit reads
+/// let dump = unsafe { Volatile::new(registers.as_ptr().cast(), 8, 4, &config) };
+/// assert!(format!("{:?}", dump).starts_with("\n40001000: | 80000001 000000FF |"));
+///
+/// let config = config.offsets(Offsets::Address);
+/// // SAFETY: as above
+/// let dump = unsafe { Volatile::new(registers.as_ptr().cast(), 8, 4, &config) };
+/// let address = format!("{:X}", registers.as_ptr() as usize);
+/// assert!(format!("{:?}", dump).lines().nth(1).unwrap().contains(&address));
+///
+/// let config = DumpConfig::portable().bytes_per_line(8);
+/// let registers = [0x0102_0304_u32.to_le(), 0x0506_0708_u32.to_le()];
+/// // SAFETY: as above
+/// let dump = unsafe { Volatile::new(registers.as_ptr().cast(), 8, 4, &config) };
+/// assert_eq!(format!("{:.6?}", dump), "\n | 04 03 02 01 08 07       | ......");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Volatile {
+    ptr: *const u8,
+    len: usize,
+    width: usize,
+    config: DumpConfig,
+}
+
+impl Volatile {
+    /// Dumps the `len` bytes at `ptr` with reads of `width` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not 1, 2, 4 or 8, or `len` not a multiple of it.
+    ///
+    /// # Safety
+    ///
+    /// For as long as the dump is used, the `len` bytes at `ptr` can be read
+    /// with volatile reads of `width` bytes, and `ptr` is aligned to `width`.
+    /// Reading registers may have side effects, e.g. clear their flags.
+    pub unsafe fn new(ptr: *const u8, len: usize, width: usize, config: &DumpConfig) -> Self {
+        assert!(
+            matches!(width, 1 | 2 | 4 | 8),
+            "the access width has to be 1, 2, 4 or 8 bytes"
+        );
+        assert!(
+            len % width == 0,
+            "the length has to be a multiple of the access width"
+        );
+        Self {
+            ptr,
+            len,
+            width,
+            config: *config,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let config = self.config;
+        let len = min(f.precision().unwrap_or(usize::MAX), self.len);
+        let (fill, width) = match f.width() {
+            Some(width) => (f.fill(), width),
+            None => (' ', config.indent),
+        };
+        let out = Indent {
+            inner: f,
+            fill,
+            width,
+        };
+        let mut dumper = Dumper::new(
+            out,
+            &DumpConfig {
+                indent: 0,
+                ..config
+            },
+        )
+        .with_addr(self.ptr as usize);
+        for offset in (0..len).step_by(self.width) {
+            let mut register = [0; 8];
+            // SAFETY: the reads are within the `len` bytes at `ptr` and aligned,
+            // guaranteed by the caller of `new`
+            unsafe {
+                let ptr = self.ptr.add(offset);
+                match self.width {
+                    1 => register[0] = ptr.read_volatile(),
+                    2 => register[..2]
+                        .copy_from_slice(&ptr.cast::<u16>().read_volatile().to_ne_bytes()),
+                    4 => register[..4]
+                        .copy_from_slice(&ptr.cast::<u32>().read_volatile().to_ne_bytes()),
+                    _ => register = ptr.cast::<u64>().read_volatile().to_ne_bytes(),
+                }
+            }
+            // the precision may end within the register
+            dumper.push(&register[..min(self.width, len - offset)])?;
+        }
+
+        dumper.finish().map(drop)
+    }
+}
+
+impl Debug for Volatile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Volatile::fmt(self, f)
+    }
+}
+
+impl Display for Volatile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Volatile::fmt(self, f)
+    }
+}
@@ -0,0 +1,249 @@
+//! Parsing dumps back into bytes.
+
+use core::cmp::min;
+use core::fmt::{Display, Formatter};
+use core::ops::Range;
+
+/// Why a dump could not be parsed, see [`Buf::parse`](crate::Buf::parse).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A line contains something other than hex digits where bytes are expected.
+    InvalidDigit {
+        /// The number of the line, starting at 1.
+        line: usize,
+    },
+    /// A line contains bytes that are masked as padding or redacted, `--` or `**`.
+    Hidden {
+        /// The number of the line, starting at 1.
+        line: usize,
+    },
+    /// A line of a [`Capture`](crate::Capture) does not start with an offset.
+    InvalidOffset {
+        /// The number of the line, starting at 1.
+        line: usize,
+    },
+    /// A line of a [`Capture`](crate::Capture) does not continue after the
+    /// bytes before it.
+    UnexpectedOffset {
+        /// The number of the line, starting at 1.
+        line: usize,
+        /// The offset after the bytes before the line.
+        expected: usize,
+        /// The offset of the line.
+        found: usize,
+    },
+    /// A [`Capture`](crate::Capture) ends with `*` instead of the offset after
+    /// the repeated lines.
+    Truncated,
+    /// The dump contains a different number of bytes than expected.
+    Length {
+        /// The number of bytes expected.
+        expected: usize,
+        /// The number of bytes in the dump.
+        found: usize,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::InvalidDigit { line } => {
+                f.write_fmt(format_args!("invalid hex digits in line {}", line))
+            }
+            ParseError::Hidden { line } => {
+                f.write_fmt(format_args!("padding or redacted bytes in line {}", line))
+            }
+            ParseError::InvalidOffset { line } => {
+                f.write_fmt(format_args!("missing offset in line {}", line))
+            }
+            ParseError::UnexpectedOffset {
+                line,
+                expected,
+                found,
+            } => f.write_fmt(format_args!(
+                "expected offset {:#x} in line {}, found {:#x}",
+                expected, line, found
+            )),
+            ParseError::Truncated => f.write_str("missing offset after repeated lines"),
+            ParseError::Length { expected, found } => {
+                f.write_fmt(format_args!("expected {} bytes, found {}", expected, found))
+            }
+        }
+    }
+}
+
+/// The error of a line, before its number is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LineError {
+    InvalidDigit,
+    Hidden,
+}
+
+impl LineError {
+    pub(crate) fn at(self, line: usize) -> ParseError {
+        match self {
+            LineError::InvalidDigit => ParseError::InvalidDigit { line },
+            LineError::Hidden => ParseError::Hidden { line },
+        }
+    }
+}
+
+/// Stores the parsed bytes, counting those that do not fit.
+pub(crate) struct Bytes<'b> {
+    pub(crate) buf: &'b mut [u8],
+    pub(crate) len: usize,
+}
+
+impl Bytes<'_> {
+    fn push(&mut self, byte: u8) {
+        if let Some(slot) = self.buf.get_mut(self.len) {
+            *slot = byte;
+        }
+        self.len += 1;
+    }
+
+    /// Appends `len` bytes repeating those in `range`.
+    pub(crate) fn repeat(&mut self, range: Range<usize>, len: usize) {
+        let end = self.len.saturating_add(len);
+        while self.len < min(end, self.buf.len()) {
+            let i = range.start + (self.len - range.start) % range.len();
+            self.buf[self.len] = self.buf.get(i).copied().unwrap_or_default();
+            self.len += 1;
+        }
+        // the bytes that do not fit are only counted
+        self.len = end;
+    }
+
+    /// Parses whitespace or comma separated words like `41 20`, `0x4120` or
+    /// `4120h`, with the bytes in the order they are written.
+    pub(crate) fn push_words<'s>(
+        &mut self,
+        words: impl Iterator<Item = &'s str>,
+    ) -> Result<(), LineError> {
+        for word in words {
+            let digits = digits(word)?;
+            for pair in digits.as_bytes().chunks(2) {
+                let pair = core::str::from_utf8(pair).unwrap_or_default();
+                self.push(u8::from_str_radix(pair, 16).map_err(|_| LineError::InvalidDigit)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a dump in the layout of this crate, `xxd` or a plain hex string.
+///
+/// Dumps of this crate may have offsets or addresses, highlights and ANSI
+/// colors, but no other columns than the bytes and their characters.
+pub(crate) fn parse(s: &str, bytes: &mut Bytes<'_>) -> Result<(), ParseError> {
+    for (i, line) in s.lines().enumerate() {
+        parse_line(line.trim(), bytes).map_err(|error| error.at(i + 1))?;
+    }
+
+    Ok(())
+}
+
+fn parse_line(line: &str, bytes: &mut Bytes<'_>) -> Result<(), LineError> {
+    let (first, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = match first.strip_suffix(':') {
+        Some(_) => rest.trim_start(),
+        None => line,
+    };
+    if let Some(row) = rest.strip_prefix('|') {
+        return parse_row(row, bytes);
+    }
+    if first.ends_with(':') {
+        // the characters of xxd follow the words after two spaces
+        let words = rest.split("  ").next().unwrap_or_default();
+        return bytes.push_words(words.split_whitespace());
+    }
+
+    bytes.push_words(rest.split(|c: char| c.is_whitespace() || c == ','))
+}
+
+/// Parses the groups of a row like ` 41 20 | 68 | A.h`, the last group is
+/// the character column unless it is made of words that have more
+/// characters than there are bytes.
+fn parse_row(row: &str, bytes: &mut Bytes<'_>) -> Result<(), LineError> {
+    let start = bytes.len;
+    let last = row.split(" | ").count() - 1;
+    for (i, group) in row.split(" | ").enumerate() {
+        if i == last {
+            let mut count = Bytes {
+                buf: &mut [],
+                len: 0,
+            };
+            let words = count.push_words(words(group));
+            if words.is_err() || visible_len(group) == bytes.len - start {
+                return Ok(());
+            }
+        }
+        bytes.push_words(words(group))?;
+    }
+
+    Ok(())
+}
+
+/// The words of a group, split at the brackets of highlights like
+/// `25[4A 61|B9]` but not at those of ANSI escape codes.
+fn words(group: &str) -> impl Iterator<Item = &str> {
+    let mut rest = group;
+    core::iter::from_fn(move || {
+        let mut previous = ' ';
+        let end = rest.char_indices().find_map(|(i, c)| {
+            let split =
+                c.is_whitespace() || matches!(c, '|' | ']') || c == '[' && previous != '\x1b';
+            previous = c;
+            split.then_some((i, c.len_utf8()))
+        });
+        let word;
+        (word, rest) = match end {
+            Some((i, len)) => (&rest[..i], &rest[i + len..]),
+            None if rest.is_empty() => return None,
+            None => (rest, ""),
+        };
+        Some(word)
+    })
+}
+
+/// The hex digits of a word, without its notation and the ANSI colors
+/// around it, e.g. `\x1b[7m41\x1b[0m`.
+fn digits(word: &str) -> Result<&str, LineError> {
+    let mut word = word;
+    while let Some(rest) = word.strip_prefix("\x1b[") {
+        word = rest.split_once('m').map_or("", |(_, rest)| rest);
+    }
+    while let Some((rest, _)) = word.rsplit_once("\x1b[") {
+        word = rest;
+    }
+    if word.starts_with("--") || word.starts_with("**") {
+        return Err(LineError::Hidden);
+    }
+    let word = word
+        .strip_prefix("0x")
+        .or_else(|| word.strip_prefix("0X"))
+        .or_else(|| word.strip_suffix('h'))
+        .unwrap_or(word);
+    if word.len() % 2 != 0 || !word.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(LineError::InvalidDigit);
+    }
+
+    Ok(word)
+}
+
+/// The number of characters without ANSI escape codes and brackets.
+fn visible_len(s: &str) -> usize {
+    let mut escape = false;
+    s.chars()
+        .filter(|&c| {
+            let visible = !escape && !matches!(c, '\x1b' | '[' | ']');
+            escape = match c {
+                '\x1b' => true,
+                'm' => false,
+                _ => escape,
+            };
+            visible
+        })
+        .count()
+}
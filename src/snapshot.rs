@@ -0,0 +1,139 @@
+//! A binary format to store buffers and dump them later.
+
+use core::fmt::{Display, Formatter};
+
+use crate::{DumpConfig, Endian, Offsets};
+
+/// The magic bytes at the start of every snapshot.
+const MAGIC: [u8; 4] = *b"MDBG";
+const VERSION: u8 = 1;
+/// Set if the snapshot was taken on a big endian target.
+const FLAG_BIG_ENDIAN: u8 = 1;
+
+/// The header of a snapshot, written by [`Buf::to_snapshot`](crate::Buf::to_snapshot).
+///
+/// A snapshot consists of the magic `MDBG`, the version, a byte of flags,
+/// two reserved bytes, the number of bytes and the base address as little
+/// endian `u64`s, followed by the bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    /// The address of the first byte.
+    pub base_address: u64,
+    /// The byte order of the target the snapshot was taken on, never
+    /// [`Endian::Native`].
+    pub endian: Endian,
+}
+
+impl Snapshot {
+    /// The number of bytes in front of the bytes of the buffer.
+    pub const HEADER_LEN: usize = 24;
+
+    /// A header for bytes at `base_address` on this target.
+    pub const fn new(base_address: u64) -> Self {
+        Self {
+            base_address,
+            endian: if cfg!(target_endian = "little") {
+                Endian::Little
+            } else {
+                Endian::Big
+            },
+        }
+    }
+
+    /// A portable config that displays the words and offsets like the target
+    /// the snapshot was taken on.
+    pub const fn config(&self) -> DumpConfig {
+        DumpConfig::portable()
+            .offsets(Offsets::Hex(8))
+            .base_offset(self.base_address as usize)
+            .endian(self.endian)
+    }
+
+    pub(crate) fn write(&self, bytes: &[u8], out: &mut [u8]) -> Result<usize, SnapshotError> {
+        let len = Self::HEADER_LEN + bytes.len();
+        let Some(out) = out.get_mut(..len) else {
+            return Err(SnapshotError::Length {
+                expected: len,
+                found: out.len(),
+            });
+        };
+        let flags = match self.endian {
+            Endian::Big => FLAG_BIG_ENDIAN,
+            Endian::Native if cfg!(target_endian = "big") => FLAG_BIG_ENDIAN,
+            _ => 0,
+        };
+        out[..4].copy_from_slice(&MAGIC);
+        out[4..8].copy_from_slice(&[VERSION, flags, 0, 0]);
+        out[8..16].copy_from_slice(&(bytes.len() as u64).to_le_bytes());
+        out[16..24].copy_from_slice(&self.base_address.to_le_bytes());
+        out[Self::HEADER_LEN..].copy_from_slice(bytes);
+        Ok(len)
+    }
+
+    /// Reads the header of `snapshot`, returning it with the bytes after it.
+    pub(crate) fn read(snapshot: &[u8]) -> Result<(Self, &[u8]), SnapshotError> {
+        if snapshot.len() < Self::HEADER_LEN {
+            return Err(SnapshotError::Length {
+                expected: Self::HEADER_LEN,
+                found: snapshot.len(),
+            });
+        }
+        let (header, bytes) = snapshot.split_at(Self::HEADER_LEN);
+        if header[..4] != MAGIC {
+            return Err(SnapshotError::Magic);
+        }
+        if header[4] != VERSION {
+            return Err(SnapshotError::Version(header[4]));
+        }
+        let u64_at = |i: usize| u64::from_le_bytes(header[i..i + 8].try_into().unwrap_or_default());
+        let len = u64_at(8);
+        if len != bytes.len() as u64 {
+            return Err(SnapshotError::Length {
+                expected: usize::try_from(len)
+                    .unwrap_or(usize::MAX)
+                    .saturating_add(Self::HEADER_LEN),
+                found: snapshot.len(),
+            });
+        }
+
+        let header = Self {
+            base_address: u64_at(16),
+            endian: match header[5] & FLAG_BIG_ENDIAN {
+                0 => Endian::Little,
+                _ => Endian::Big,
+            },
+        };
+        Ok((header, bytes))
+    }
+}
+
+/// Why a snapshot could not be written or read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    /// The bytes do not start with the magic `MDBG`.
+    Magic,
+    /// The snapshot has a version this crate does not know.
+    Version(u8),
+    /// The snapshot or the buffer it is written to has the wrong length.
+    Length {
+        /// The length of the snapshot.
+        expected: usize,
+        /// The length of the bytes.
+        found: usize,
+    },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SnapshotError::Magic => f.write_str("not a snapshot"),
+            SnapshotError::Version(version) => {
+                f.write_fmt(format_args!("unknown snapshot version {}", version))
+            }
+            SnapshotError::Length { expected, found } => f.write_fmt(format_args!(
+                "expected a snapshot of {} bytes, found {}",
+                expected, found
+            )),
+        }
+    }
+}
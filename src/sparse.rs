@@ -0,0 +1,69 @@
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::{Dump, DumpConfig};
+
+/// A hex dump of non-contiguous segments of memory, e.g. scatter-gather
+/// buffers or partially captured memory.
+///
+/// Each segment is an offset and its bytes, gaps between segments are
+/// marked with their length instead of filler bytes.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, Offsets, Sparse};
+///
+/// let segments: [(usize, &[u8]); 2] = [(0x1000, b"header bytes"), (0x2000, b"payload")];
+/// let config = DumpConfig::portable().bytes_per_line(8).offsets(Offsets::Hex(4));
+/// assert_eq!(format!("{:?}", Sparse::new(&segments, &config)), concat!(
+///     "\n1000: | 68 65 61 64 65 72 20 62 | header.b",
+///     "\n1008: | 79 74 65 73             | ytes",
+///     "\n… gap of 4084 bytes …",
+///     "\n2000: | 70 61 79 6C 6F 61 64    | payload",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Sparse<'a> {
+    segments: &'a [(usize, &'a [u8])],
+    config: DumpConfig,
+}
+
+impl<'a> Sparse<'a> {
+    /// Dumps `segments` of `(offset, bytes)`, ordered by offset.
+    pub fn new(segments: &'a [(usize, &'a [u8])], config: &DumpConfig) -> Self {
+        Self {
+            segments,
+            config: *config,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut end = None;
+        for &(offset, bytes) in self.segments {
+            match end {
+                Some(end) if offset > end => {
+                    f.write_fmt(format_args!("\n… gap of {} bytes …", offset - end))?
+                }
+                _ => {}
+            }
+            end = Some(offset + bytes.len());
+
+            let config = self
+                .config
+                .base_offset(self.config.base_offset.wrapping_add(offset));
+            Dump::new(bytes, &config).with_addr(offset).fmt_table(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Sparse<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Sparse::fmt(self, f)
+    }
+}
+
+impl Display for Sparse<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Sparse::fmt(self, f)
+    }
+}
@@ -0,0 +1,246 @@
+//! Hex dumps of bytes that arrive piece by piece, without collecting them.
+
+use core::borrow::Borrow;
+use core::cmp::min;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::dump::{Indent, MAX_LINE};
+use crate::{Dump, DumpConfig};
+
+/// Writes the lines of a hex dump as soon as their bytes arrive, e.g. for
+/// messages received in DMA chunks.
+///
+/// Lines are at most 256 bytes long, a longer
+/// [`bytes_per_line`](DumpConfig::bytes_per_line) is cut to 256. They are
+/// not aligned to an address, their offsets start at the
+/// [`base_offset`](DumpConfig::base_offset) and continue across the chunks.
+/// The bytes have no address, [`Offsets::Address`](crate::Offsets::Address)
+/// shows their offsets. Only the lines are written,
+/// [squeezed](DumpConfig::squeeze) if enabled.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, Dumper, Offsets};
+///
+/// let config = DumpConfig::portable().bytes_per_line(4).offsets(Offsets::Hex(2));
+/// let mut dumper = Dumper::new(String::new(), &config);
+/// dumper.push(b"DMA ")?;
+/// dumper.push(b"ch")?;
+/// dumper.push(b"unks")?;
+/// assert_eq!(dumper.finish()?, concat!(
+///     "\n00: | 44 4D 41 20 | DMA.",
+///     "\n04: | 63 68 75 6E | chun",
+///     "\n08: | 6B 73       | ks",
+/// ));
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+///
+/// ```rust
+/// use memdbg::{DumpConfig, Dumper};
+///
+/// let config = DumpConfig::portable().bytes_per_line(300).squeeze(true);
+/// let mut dumper = Dumper::new(String::new(), &config);
+/// dumper.push(&[0; 1200])?;
+/// let dump = dumper.finish()?;
+/// let lines: Vec<_> = dump.lines().skip(1).collect();
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines[0].matches("00").count(), 256);
+/// assert_eq!(lines[1], "* 3 identical lines");
+/// assert_eq!(lines[2].matches("00").count(), 1200 - 4 * 256);
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+pub struct Dumper<W> {
+    out: W,
+    config: DumpConfig,
+    /// The bytes of the current line.
+    line: [u8; MAX_LINE],
+    len: usize,
+    /// The last line written, repeated by the squeezed lines.
+    previous: [u8; MAX_LINE],
+    /// The offset of the current line.
+    start: usize,
+    squeezed: usize,
+    /// The address of the first byte, shown by [`Offsets::Address`](crate::Offsets::Address).
+    addr: usize,
+}
+
+impl<W: Write> Dumper<W> {
+    /// Writes the lines into `out`.
+    pub fn new(out: W, config: &DumpConfig) -> Self {
+        Self {
+            out,
+            config: DumpConfig {
+                address_alignment: false,
+                bytes_per_line: min(config.bytes_per_line, MAX_LINE),
+                ..*config
+            },
+            line: [0; MAX_LINE],
+            len: 0,
+            previous: [0; MAX_LINE],
+            start: 0,
+            squeezed: 0,
+            addr: 0,
+        }
+    }
+
+    /// Shows the bytes at `addr` instead of their offsets with
+    /// [`Offsets::Address`](crate::Offsets::Address).
+    pub(crate) fn with_addr(mut self, addr: usize) -> Self {
+        self.addr = addr;
+        self
+    }
+
+    /// Adds `bytes` and writes the lines they complete.
+    pub fn push(&mut self, mut bytes: &[u8]) -> core::fmt::Result {
+        let line_len = self.config.bytes_per_line;
+        while !bytes.is_empty() {
+            let (head, rest) = bytes.split_at(min(line_len - self.len, bytes.len()));
+            self.line[self.len..self.len + head.len()].copy_from_slice(head);
+            self.len += head.len();
+            bytes = rest;
+            if self.len == line_len {
+                self.write_line()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the last, incomplete line and returns the writer.
+    pub fn finish(mut self) -> Result<W, core::fmt::Error> {
+        if self.len != 0 {
+            self.write_line()?;
+        }
+        self.write_squeezed()?;
+        Ok(self.out)
+    }
+
+    /// A dump of the line at `offset`.
+    fn line_at<'a>(&self, bytes: &'a [u8], offset: usize) -> Dump<'a> {
+        let config = self.config;
+        let config = config.base_offset(config.base_offset.wrapping_add(offset));
+        Dump::new(bytes, &config).with_addr(self.addr.wrapping_add(offset))
+    }
+
+    fn write_line(&mut self) -> core::fmt::Result {
+        let len = core::mem::take(&mut self.len);
+        let line = self.line_at(&self.line[..len], self.start);
+        let previous = (self.start != 0).then_some(&self.previous[..len]);
+        if line.repeats(0..len, previous) {
+            self.squeezed += 1;
+        } else {
+            self.write_squeezed()?;
+            self.line_at(&self.line[..len], self.start)
+                .write_row(&mut indented(&mut self.out, &self.config), 0..len)?;
+            self.previous = self.line;
+        }
+        self.start += len;
+
+        Ok(())
+    }
+
+    fn write_squeezed(&mut self) -> core::fmt::Result {
+        let line_len = self.config.bytes_per_line;
+        // the previous line is the last of the squeezed lines
+        self.line_at(
+            &self.previous[..line_len],
+            self.start.wrapping_sub(line_len),
+        )
+        .write_squeezed(
+            &mut indented(&mut self.out, &self.config),
+            core::mem::take(&mut self.squeezed),
+            0,
+        )
+    }
+}
+
+/// A hex dump of the bytes of an iterator, e.g. of a ring buffer or a
+/// streaming decoder, written line by line without collecting them.
+///
+/// The iterator is cloned whenever the dump is formatted, the lines are
+/// written like those of a [`Dumper`]. The width of the formatter indents
+/// them and its precision limits the number of bytes like those of a
+/// [`Dump`].
+///
+/// ```rust
+/// use memdbg::{DumpConfig, IterDump, Offsets};
+///
+/// let ring = *b"cdab";
+/// let bytes = ring[2..].iter().chain(&ring[..2]);
+/// let config = DumpConfig::portable().bytes_per_line(2).offsets(Offsets::Hex(2));
+/// let dump = IterDump::new(bytes, &config);
+/// assert_eq!(format!("{:?}", dump), "\n00: | 61 62 | ab\n02: | 63 64 | cd");
+/// assert_eq!(format!("{:.3?}", dump), "\n00: | 61 62 | ab\n02: | 63    | c");
+/// ```
+#[derive(Clone)]
+pub struct IterDump<I> {
+    bytes: I,
+    config: DumpConfig,
+}
+
+impl<I> IterDump<I>
+where
+    I: Iterator + Clone,
+    I::Item: Borrow<u8>,
+{
+    /// Dumps the bytes of `bytes`, e.g. an iterator of `u8` or `&u8`.
+    pub fn new(bytes: I, config: &DumpConfig) -> Self {
+        Self {
+            bytes,
+            config: *config,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let config = self.config;
+        let len = f.precision().unwrap_or(usize::MAX);
+        let (fill, width) = match f.width() {
+            Some(width) => (f.fill(), width),
+            None => (' ', config.indent),
+        };
+        let out = Indent {
+            inner: f,
+            fill,
+            width,
+        };
+        let mut dumper = Dumper::new(
+            out,
+            &DumpConfig {
+                indent: 0,
+                ..config
+            },
+        );
+        for byte in self.bytes.clone().take(len) {
+            dumper.push(&[*byte.borrow()])?;
+        }
+        dumper.finish().map(drop)
+    }
+}
+
+impl<I> Debug for IterDump<I>
+where
+    I: Iterator + Clone,
+    I::Item: Borrow<u8>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        IterDump::fmt(self, f)
+    }
+}
+
+impl<I> Display for IterDump<I>
+where
+    I: Iterator + Clone,
+    I::Item: Borrow<u8>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        IterDump::fmt(self, f)
+    }
+}
+
+/// Indents the lines written to `out` by the [`indent`](DumpConfig::indent) of `config`.
+fn indented<'w, W: Write>(out: &'w mut W, config: &DumpConfig) -> Indent<'w, W> {
+    Indent {
+        inner: out,
+        fill: ' ',
+        width: config.indent,
+    }
+}
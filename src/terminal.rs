@@ -0,0 +1,144 @@
+//! The width of the terminal for the `std` feature.
+
+use std::io::IsTerminal;
+
+/// The number of columns of the terminal on standard output, `None` if
+/// standard output is not a terminal.
+///
+/// Asks the terminal for its size and falls back to the `COLUMNS`
+/// environment variable if it does not answer.
+pub(crate) fn width() -> Option<usize> {
+    let stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return None;
+    }
+    query(&stdout).or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn query(stdout: &std::io::Stdout) -> Option<usize> {
+    use core::ffi::{c_int, c_ulong, c_ushort};
+    use std::os::fd::AsRawFd;
+
+    #[repr(C)]
+    struct Winsize {
+        rows: c_ushort,
+        columns: c_ushort,
+        x_pixels: c_ushort,
+        y_pixels: c_ushort,
+    }
+
+    #[cfg(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64",
+        ))
+    ))]
+    const TIOCGWINSZ: c_ulong = 0x5413;
+    #[cfg(not(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "sparc64",
+        ))
+    )))]
+    const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+    extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    }
+
+    let mut size = Winsize {
+        rows: 0,
+        columns: 0,
+        x_pixels: 0,
+        y_pixels: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a `winsize` to the pointer.
+    let result = unsafe { ioctl(stdout.as_raw_fd(), TIOCGWINSZ, &mut size as *mut Winsize) };
+    (result == 0 && size.columns > 0).then_some(usize::from(size.columns))
+}
+
+#[cfg(windows)]
+fn query(stdout: &std::io::Stdout) -> Option<usize> {
+    use core::ffi::c_void;
+    use std::os::windows::io::AsRawHandle;
+
+    #[repr(C)]
+    struct Coord {
+        x: i16,
+        y: i16,
+    }
+
+    #[repr(C)]
+    struct SmallRect {
+        left: i16,
+        top: i16,
+        right: i16,
+        bottom: i16,
+    }
+
+    #[repr(C)]
+    struct ConsoleScreenBufferInfo {
+        size: Coord,
+        cursor_position: Coord,
+        attributes: u16,
+        window: SmallRect,
+        maximum_window_size: Coord,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetConsoleScreenBufferInfo(
+            console: *mut c_void,
+            info: *mut ConsoleScreenBufferInfo,
+        ) -> i32;
+    }
+
+    let mut info = ConsoleScreenBufferInfo {
+        size: Coord { x: 0, y: 0 },
+        cursor_position: Coord { x: 0, y: 0 },
+        attributes: 0,
+        window: SmallRect {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        },
+        maximum_window_size: Coord { x: 0, y: 0 },
+    };
+    // SAFETY: the handle is the console of standard output and `info` is
+    // a valid `CONSOLE_SCREEN_BUFFER_INFO` to write to.
+    let result = unsafe { GetConsoleScreenBufferInfo(stdout.as_raw_handle().cast(), &mut info) };
+    let columns = i32::from(info.window.right) - i32::from(info.window.left) + 1;
+    (result != 0 && columns > 0).then(|| columns as usize)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    windows,
+)))]
+fn query(_: &std::io::Stdout) -> Option<usize> {
+    None
+}
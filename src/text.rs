@@ -0,0 +1,192 @@
+/// How the character column decodes bytes, see [`DumpConfig::charset`](crate::DumpConfig::charset).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// Printable ASCII, everything else is a placeholder.
+    Ascii,
+    /// UTF-8, multi byte characters are displayed at their first byte and
+    /// their continuation bytes are marked.
+    Utf8,
+    /// ISO 8859-1, the first 256 Unicode code points.
+    Latin1,
+    /// Code page 437 of the IBM PC and DOS, including the glyphs for control characters.
+    Cp437,
+    /// EBCDIC code page 037, as used by IBM mainframes.
+    Ebcdic,
+    /// Printable ASCII, control characters and the space as their Unicode
+    /// control pictures, `␀` or `␊`, for terminal and serial protocols.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Charset, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().charset(Charset::ControlPictures);
+    /// let dump = format!("{:?}", Buf(*b"AT\r\nOK\x1b[0m").display_with(&config));
+    /// assert!(dump.contains("AT␍␊OK␛[0m"));
+    /// ```
+    ControlPictures,
+    /// UTF-16 little endian, as in Windows APIs and many of their file
+    /// formats. Characters are displayed at the first byte of their code
+    /// units, counted from the start of the dump, and the other bytes are
+    /// marked as continuations.
+    ///
+    /// ```rust
+    /// use memdbg::{Buf, Charset, DumpConfig};
+    ///
+    /// let config = DumpConfig::portable().charset(Charset::Utf16Le).continuation_placeholder(' ');
+    /// let dump = format!("{:?}", Buf(*b"W\0i\0d\0e\0\x3D\xD8\x00\xDE").display_with(&config));
+    /// assert!(dump.ends_with("| W i d e 😀   "));
+    /// ```
+    Utf16Le,
+    /// UTF-16 big endian, see [`Charset::Utf16Le`].
+    Utf16Be,
+}
+
+/// A decoded cell of the character column.
+pub(crate) enum Cell {
+    Char(char),
+    /// A byte that belongs to a character displayed at an earlier byte.
+    Continuation,
+    /// A byte without a printable representation.
+    None,
+}
+
+impl Charset {
+    /// Decodes the byte at `index`, looking at its neighbours for multi byte characters.
+    pub(crate) fn decode(self, buf: &[u8], index: usize) -> Cell {
+        let byte = buf[index];
+        match self {
+            Charset::Ascii => ascii(byte),
+            Charset::Utf8 if byte.is_ascii() => ascii(byte),
+            Charset::Utf8 => utf8(buf, index),
+            // the soft hyphen is invisible
+            Charset::Latin1 if byte == 0xAD => Cell::None,
+            Charset::Latin1 => printable(byte as char),
+            Charset::Cp437 => match byte {
+                0x01..=0x1F => Cell::Char(CP437_CONTROL[byte as usize - 1]),
+                0x7F => Cell::Char('⌂'),
+                0x80..=0xFF => printable(CP437_HIGH[byte as usize - 0x80]),
+                _ => ascii(byte),
+            },
+            Charset::Ebcdic => printable(EBCDIC[byte as usize]),
+            Charset::Utf16Le => utf16(buf, index, true),
+            Charset::Utf16Be => utf16(buf, index, false),
+            Charset::ControlPictures => match byte {
+                0x00..=0x20 => Cell::Char(char::from_u32(0x2400 + byte as u32).unwrap_or('.')),
+                0x7F => Cell::Char('␡'),
+                _ => ascii(byte),
+            },
+        }
+    }
+}
+
+fn ascii(byte: u8) -> Cell {
+    if byte.is_ascii_graphic() {
+        Cell::Char(byte as char)
+    } else {
+        Cell::None
+    }
+}
+
+fn utf8(buf: &[u8], index: usize) -> Cell {
+    for start in index.saturating_sub(3)..index {
+        if let Some((_, len)) = utf8_char(buf, start) {
+            if start + len > index {
+                return Cell::Continuation;
+            }
+        }
+    }
+
+    match utf8_char(buf, index) {
+        Some((char, _)) => printable(char),
+        None => Cell::None,
+    }
+}
+
+/// Decodes the character starting at `index` and its length in bytes.
+fn utf8_char(buf: &[u8], index: usize) -> Option<(char, usize)> {
+    let len = match buf[index] {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+    let bytes = buf.get(index..index + len)?;
+    let char = core::str::from_utf8(bytes).ok()?.chars().next()?;
+    Some((char, len))
+}
+
+fn utf16(buf: &[u8], index: usize, little: bool) -> Cell {
+    let unit = index - index % 2;
+    // the low surrogate of a pair starting at the unit before
+    let (start, char) = match unit.checked_sub(2).and_then(|i| utf16_char(buf, i, little)) {
+        Some((char, 4)) => (unit - 2, Some(char)),
+        _ => (unit, utf16_char(buf, unit, little).map(|(char, _)| char)),
+    };
+
+    match char.map(printable) {
+        Some(cell) if index == start => cell,
+        Some(Cell::Char(_)) => Cell::Continuation,
+        _ => Cell::None,
+    }
+}
+
+/// Decodes the character starting at `index` and its length in bytes.
+fn utf16_char(buf: &[u8], index: usize, little: bool) -> Option<(char, usize)> {
+    let unit = |i: usize| {
+        let bytes = [*buf.get(i)?, *buf.get(i + 1)?];
+        Some(match little {
+            true => u16::from_le_bytes(bytes),
+            false => u16::from_be_bytes(bytes),
+        })
+    };
+    let high = unit(index)?;
+    if !(0xD800..0xDC00).contains(&high) {
+        return Some((char::from_u32(high.into())?, 2));
+    }
+    let low = unit(index + 2).filter(|low| (0xDC00..0xE000).contains(low))?;
+    let char = 0x10000 + ((u32::from(high) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+    Some((char::from_u32(char)?, 4))
+}
+
+fn printable(char: char) -> Cell {
+    if char.is_control() || char.is_whitespace() {
+        Cell::None
+    } else {
+        Cell::Char(char)
+    }
+}
+
+/// Glyphs of code page 437 for `0x01..=0x1F`.
+const CP437_CONTROL: [char; 31] = [
+    '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄', '↕', '‼',
+    '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// Code page 437 for `0x80..=0xFF`.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ',
+    'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ',
+    'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕',
+    '╣', '║', '╗', '╝', '╜', '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦',
+    '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐',
+    '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±',
+    '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// EBCDIC code page 037, `'\0'` for control characters.
+const EBCDIC: [char; 256] = [
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    ' ', '\u{a0}', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|', '&', 'é',
+    'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬', '-', '/', 'Â', 'Ä', 'À',
+    'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?', 'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï',
+    'Ì', '`', ':', '#', '@', '\'', '=', '"', 'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«',
+    '»', 'ð', 'ý', 'þ', '±', '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸',
+    'Æ', '¤', 'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®', '^',
+    '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×', '{', 'A', 'B', 'C',
+    'D', 'E', 'F', 'G', 'H', 'I', '\0', 'ô', 'ö', 'ò', 'ó', 'õ', '}', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ', '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ', '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü',
+    'Ù', 'Ú', '\0',
+];
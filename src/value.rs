@@ -0,0 +1,251 @@
+//! Hex dumps of the bytes of values.
+
+use core::cmp::{max, min};
+use core::fmt::{Debug, Display, Formatter};
+use core::mem::MaybeUninit;
+use core::ops::Range;
+
+use crate::dump::{Indent, MAX_LINE};
+use crate::{Buf, Dump, DumpConfig, HexDump};
+
+/// Types without uninitialized bytes, whose values can be viewed as bytes,
+/// see [`dump_value`].
+///
+/// # Safety
+///
+/// Every byte of every value of the type is initialized, i.e. the type has
+/// no padding, like a `#[repr(C)]` struct of such types whose fields follow
+/// each other without gaps. The type contains no `UnsafeCell`, as its bytes
+/// are read through a shared reference.
+///
+/// ```rust
+/// #[repr(C)]
+/// struct Descriptor {
+///     address: u32,
+///     len: u16,
+///     flags: u16,
+/// }
+///
+/// // SAFETY: the fields have no gaps between them, fill the alignment and
+/// // are no cells
+/// unsafe impl memdbg::NoUninit for Descriptor {}
+///
+/// let descriptor = Descriptor {
+///     address: 0x2000_0000_u32.to_le(),
+///     len: 64_u16.to_le(),
+///     flags: 1_u16.to_le(),
+/// };
+/// assert_eq!(format!("{:?}", memdbg::dump_value(&descriptor)), "00 00 00 20 40 00 01 00");
+/// ```
+pub unsafe trait NoUninit {}
+
+macro_rules! no_uninit {
+    ($($ty:ty),*) => {
+        $(
+            // SAFETY: primitives have no padding and are no cells
+            unsafe impl NoUninit for $ty {}
+        )*
+    };
+}
+
+no_uninit!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char);
+
+// SAFETY: the elements of arrays follow each other without gaps
+unsafe impl<T: NoUninit, const N: usize> NoUninit for [T; N] {}
+
+// SAFETY: a buffer is a byte array
+unsafe impl<const N: usize> NoUninit for Buf<N> {}
+
+/// Dumps the bytes of `value` without a wrapper type or [`buf_dbg`](crate::buf_dbg),
+/// with the formatting of [`Buf`].
+///
+/// ```rust
+/// let value = 0x1234_5678_u32;
+/// assert_eq!(format!("{:?}", memdbg::dump_value(&value.to_be())), "12 34 56 78");
+/// ```
+pub fn dump_value<T: NoUninit>(value: &T) -> HexDump<'_> {
+    let ptr = (value as *const T).cast::<u8>();
+    // SAFETY: all bytes of `value` are initialized as `T` is `NoUninit`
+    HexDump(unsafe { core::slice::from_raw_parts(ptr, core::mem::size_of::<T>()) })
+}
+
+/// A hex dump of a slice element by element, e.g. of an array of packet
+/// descriptors.
+///
+/// Each element is headed by its index and separated from the one before
+/// it by a blank line. The offsets continue from element to element.
+///
+/// ```rust
+/// use memdbg::{DumpConfig, Elements, Offsets};
+///
+/// let descriptors = [[0x2000_0000_u32, 64], [0x2000_0040, 32]].map(|d| d.map(u32::to_le));
+/// let config = DumpConfig::portable().bytes_per_line(8).offsets(Offsets::Hex(4));
+/// assert_eq!(format!("{:?}", Elements::new(&descriptors, &config)), concat!(
+///     "[0]\n",
+///     "0000: | 00 00 00 20 40 00 00 00 | ....@...\n",
+///     "\n",
+///     "[1]\n",
+///     "0008: | 40 00 00 20 20 00 00 00 | @.......",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Elements<'a, T> {
+    elements: &'a [T],
+    config: DumpConfig,
+}
+
+impl<'a, T: NoUninit> Elements<'a, T> {
+    /// Dumps each of `elements`.
+    pub fn new(elements: &'a [T], config: &DumpConfig) -> Self {
+        Self {
+            elements,
+            config: *config,
+        }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let size = core::mem::size_of::<T>();
+        for (i, element) in self.elements.iter().enumerate() {
+            if i != 0 {
+                f.write_str("\n\n")?;
+            }
+            f.write_fmt(format_args!("[{}]", i))?;
+            let config = self
+                .config
+                .base_offset(self.config.base_offset.wrapping_add(i * size));
+            Dump::new(dump_value(element).0, &config).fmt_table(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: NoUninit> Debug for Elements<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Elements::fmt(self, f)
+    }
+}
+
+impl<T: NoUninit> Display for Elements<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Elements::fmt(self, f)
+    }
+}
+
+/// A hex dump of partially initialized bytes, e.g. of a receive buffer or a
+/// value under construction.
+///
+/// Only the initialized bytes are read, the others are displayed as `??`
+/// and blank in the character column. Like those of a
+/// [`Dumper`](crate::Dumper), lines are at most 256 bytes long, a longer
+/// [`bytes_per_line`](DumpConfig::bytes_per_line) is cut to 256, and not
+/// aligned to an address. The precision of the formatter limits the number
+/// of bytes like that of a [`Dump`].
+///
+/// ```rust
+/// use core::mem::MaybeUninit;
+/// use memdbg::{DumpConfig, Uninit};
+///
+/// let mut buf = [MaybeUninit::<u8>::uninit(); 6];
+/// buf[..3].copy_from_slice(&[MaybeUninit::new(b'r'); 3]);
+/// let initialized = [0..3];
+/// // SAFETY: the first three bytes are initialized
+/// let dump = unsafe { Uninit::new(&buf, &initialized, &DumpConfig::portable().bytes_per_line(8)) };
+/// assert_eq!(format!("{:?}", dump), "\n | 72 72 72 ?? ?? ??       | rrr   ");
+/// assert_eq!(format!("{:.4?}", dump), "\n | 72 72 72 ??             | rrr ");
+/// ```
+#[derive(Clone, Copy)]
+pub struct Uninit<'a> {
+    bytes: &'a [MaybeUninit<u8>],
+    initialized: &'a [Range<usize>],
+    config: DumpConfig,
+}
+
+impl<'a> Uninit<'a> {
+    /// Dumps `bytes`, of which those in the `initialized` ranges are
+    /// initialized.
+    ///
+    /// # Safety
+    ///
+    /// The bytes in the `initialized` ranges are initialized.
+    pub unsafe fn new(
+        bytes: &'a [MaybeUninit<u8>],
+        initialized: &'a [Range<usize>],
+        config: &DumpConfig,
+    ) -> Self {
+        Self {
+            bytes,
+            initialized,
+            config: *config,
+        }
+    }
+
+    /// Dumps the bytes of `value`, see [`Uninit::new`].
+    ///
+    /// # Safety
+    ///
+    /// The bytes of `value` in the `initialized` ranges are initialized.
+    pub unsafe fn of<T>(
+        value: &'a MaybeUninit<T>,
+        initialized: &'a [Range<usize>],
+        config: &DumpConfig,
+    ) -> Self {
+        let ptr = value.as_ptr().cast::<MaybeUninit<u8>>();
+        // SAFETY: `value` has the size of `T`, and `MaybeUninit<u8>` holds any byte
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, core::mem::size_of::<T>()) };
+        // SAFETY: guaranteed by the caller
+        unsafe { Self::new(bytes, initialized, config) }
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let config = DumpConfig {
+            address_alignment: false,
+            bytes_per_line: min(self.config.bytes_per_line, MAX_LINE),
+            ..self.config
+        };
+        let precision = f.precision().unwrap_or(usize::MAX);
+        let (fill, width) = match f.width() {
+            Some(width) => (f.fill(), width),
+            None => (' ', config.indent),
+        };
+        let mut out = Indent {
+            inner: f,
+            fill,
+            width,
+        };
+        let line_len = config.bytes_per_line;
+        let addr = self.bytes.as_ptr() as usize;
+        let len = min(precision, self.bytes.len());
+        for start in (0..len).step_by(line_len) {
+            let end = min(start + line_len, len);
+            let (mut line, mut uninit) = ([0; MAX_LINE], [true; MAX_LINE]);
+            for range in self.initialized {
+                for i in max(range.start, start)..min(range.end, end) {
+                    // SAFETY: the byte is initialized, guaranteed by the caller of `new`
+                    line[i - start] = unsafe { self.bytes[i].assume_init() };
+                    uninit[i - start] = false;
+                }
+            }
+
+            let config = config.base_offset(config.base_offset.wrapping_add(start));
+            Dump::new(&line[..end - start], &config)
+                .with_addr(addr.wrapping_add(start))
+                .uninit(&uninit[..end - start])
+                .write_row(&mut out, 0..end - start)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Uninit<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Uninit::fmt(self, f)
+    }
+}
+
+impl Display for Uninit<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Uninit::fmt(self, f)
+    }
+}
@@ -0,0 +1,69 @@
+//! YAML output for fixture files and reports.
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::dump::Indent;
+use crate::Dump;
+
+/// A hex dump as a YAML literal block scalar, to embed it in fixtures and
+/// reports.
+///
+/// Created by [`Dump::yaml`]. The dump is restricted to printable ASCII
+/// like [`DumpConfig::plain`](crate::DumpConfig::plain), since a literal
+/// block cannot escape anything, and its lines are indented by two spaces
+/// more than the key of the block, which is at the start of a line unless
+/// set by [`Yaml::indent`]. The block has an explicit indentation
+/// indicator, so the spaces in front of the first line are kept, and
+/// strips the final line break.
+///
+/// ```rust
+/// use memdbg::{Buf, DumpConfig};
+///
+/// let buf = Buf(*b"key: \"value\"\n");
+/// let dump = buf.display_with(&DumpConfig::portable().bytes_per_line(8));
+/// assert_eq!(format!("fixture: {}", dump.yaml()), concat!(
+///     "fixture: |2-\n",
+///     "   | 6B 65 79 3A 20 22 76 61 | key:.\"va\n",
+///     "   | 6C 75 65 22 0A          | lue\".",
+/// ));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Yaml<'a> {
+    dump: Dump<'a>,
+    indent: usize,
+}
+
+impl<'a> Yaml<'a> {
+    pub(crate) fn new(dump: Dump<'a>) -> Self {
+        Self { dump, indent: 0 }
+    }
+
+    /// Sets the indentation of the key of the block, for blocks in nested
+    /// mappings or sequences.
+    pub fn indent(mut self, width: usize) -> Self {
+        self.indent = width;
+        self
+    }
+
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut indented = Indent {
+            inner: f,
+            fill: ' ',
+            width: self.indent + 2,
+        };
+        indented.write_str("|2-\n")?;
+        self.dump.write(&mut indented)
+    }
+}
+
+impl Debug for Yaml<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Yaml::fmt(self, f)
+    }
+}
+
+impl Display for Yaml<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Yaml::fmt(self, f)
+    }
+}